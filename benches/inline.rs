@@ -0,0 +1,35 @@
+use std::hint::black_box;
+use std::rc::Rc;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use our_string::comrades::RcBytes;
+use our_string::OurBytes;
+
+fn bench_inline<const N: usize>(c: &mut Criterion) {
+    let mut group = c.benchmark_group(format!("inline/{N}"));
+    for len in [0, 1, N / 2, N.min(254)] {
+        let content = vec![b'x'; len];
+        group.bench_with_input(BenchmarkId::new("from", len), &content, |b, content| {
+            b.iter(|| OurBytes::<RcBytes, N>::from(black_box(content.as_slice())))
+        });
+        let value = OurBytes::<RcBytes, N>::from(content.as_slice());
+        group.bench_with_input(BenchmarkId::new("clone", len), &value, |b, value| {
+            b.iter(|| black_box(value).clone())
+        });
+    }
+    group.finish();
+}
+
+fn bench_outline(c: &mut Criterion) {
+    let mut group = c.benchmark_group("outline");
+    let content = vec![b'x'; 1000];
+    group.bench_function("from/rc_bytes", |b| b.iter(|| OurBytes::<RcBytes, 23>::from(black_box(content.as_slice()))));
+    group.bench_function("from/rc_slice", |b| b.iter(|| OurBytes::<Rc<[u8]>, 22>::from(black_box(content.as_slice()))));
+    let value = OurBytes::<RcBytes, 23>::from(content.as_slice());
+    group.bench_function("clone/rc_bytes", |b| b.iter(|| black_box(&value).clone()));
+    group.finish();
+}
+
+criterion_group!(benches, bench_inline::<16>, bench_inline::<128>, bench_inline::<254>, bench_outline);
+criterion_main!(benches);
@@ -0,0 +1,58 @@
+use alloc::string::String;
+
+use arbitrary::{Arbitrary, Unstructured, Result};
+
+use crate::{Comrade, OurBytes, OurString};
+
+/// Generates content biased towards the inline/outline boundary (`N`, capped at the 254 byte inline limit).
+fn content<'a, const N: usize>(u: &mut Unstructured<'a>) -> Result<&'a [u8]> {
+    match u.int_in_range(0..=1)? {
+        0 => <&[u8]>::arbitrary(u),
+        _ => {
+            let limit = N.min(u8::MAX as usize - 1);
+            let len = u.int_in_range(limit.saturating_sub(1)..=limit + 1)?;
+            u.bytes(len.min(u.len()))
+        }
+    }
+}
+
+/// Generates string content biased towards the inline/outline boundary (`N`, capped at the 254 byte inline limit).
+fn str_content<const N: usize>(u: &mut Unstructured<'_>) -> Result<String> {
+    match u.int_in_range(0..=1)? {
+        0 => String::arbitrary(u),
+        _ => Ok(content::<N>(u)?.iter().map(|&x| (x & 0x7f) as char).collect()),
+    }
+}
+
+impl<'a, T: Comrade, const N: usize> Arbitrary<'a> for OurBytes<T, N> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let value = content::<N>(u)?;
+        Ok(match bool::arbitrary(u)? {
+            true => OurBytes::from(value),
+            false => OurBytes::from(T::from_slice(value)),
+        })
+    }
+}
+
+impl<'a, T: Comrade, const N: usize> Arbitrary<'a> for OurString<T, N> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let value = str_content::<N>(u)?;
+        Ok(match bool::arbitrary(u)? {
+            true => OurString::from(value.as_str()),
+            false => OurString::from_utf8(OurBytes::from(T::from_slice(value.as_bytes()))).unwrap(),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for crate::comrades::RcBytes {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        <&[u8]>::arbitrary(u).map(Self::from)
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<'a> Arbitrary<'a> for crate::comrades::ArcBytes {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        <&[u8]>::arbitrary(u).map(Self::from)
+    }
+}
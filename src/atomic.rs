@@ -0,0 +1,143 @@
+use core::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
+use core::marker::PhantomData;
+use core::fmt::{self, Debug};
+
+use alloc::boxed::Box;
+
+use crate::{Comrade, OurString};
+
+/// A shared cell holding an [`OurString`] which can be read and replaced atomically.
+///
+/// Reading (via [`load`](Self::load)) never waits on writers and simply clones the current value, which is cheap for shared content.
+/// Writing (via [`store`](Self::store), [`swap`](Self::swap), or [`compare_and_swap`](Self::compare_and_swap)) publishes the new value immediately,
+/// but then has to wait for in-progress reads of the old value to finish before it can be reclaimed.
+/// Writers are serialized with respect to each other, so this is best suited for values which are read often but changed rarely.
+pub struct AtomicOurString<T: Comrade, const N: usize> {
+    ptr: AtomicPtr<OurString<T, N>>,
+    /// Readers register in `readers[epoch % 2]`; writers flip the epoch so that they only wait on readers which may have seen the old value.
+    epoch: AtomicUsize,
+    readers: [AtomicUsize; 2],
+    writer: AtomicBool,
+    phantom: PhantomData<*mut OurString<T, N>>, // we implement Send/Sync manually
+}
+
+impl<T: Comrade, const N: usize> AtomicOurString<T, N> {
+    /// Creates a new [`AtomicOurString`] holding the given value.
+    pub fn new(value: OurString<T, N>) -> Self {
+        Self {
+            ptr: AtomicPtr::new(Box::into_raw(Box::new(value))),
+            epoch: AtomicUsize::new(0),
+            readers: [AtomicUsize::new(0), AtomicUsize::new(0)],
+            writer: AtomicBool::new(false),
+            phantom: PhantomData,
+        }
+    }
+    /// Gets a mutable reference to the current value, which is safe because no other references to the cell exist.
+    pub fn get_mut(&mut self) -> &mut OurString<T, N> {
+        unsafe { &mut **self.ptr.get_mut() }
+    }
+    /// Consumes the cell and returns the current value.
+    pub fn into_inner(self) -> OurString<T, N> {
+        let this = core::mem::ManuallyDrop::new(self);
+        unsafe { *Box::from_raw(this.ptr.load(Ordering::Relaxed)) }
+    }
+    /// Gets a snapshot of the current value.
+    pub fn load(&self) -> OurString<T, N> where T: Clone {
+        let _slot = loop {
+            let epoch = self.epoch.load(Ordering::SeqCst);
+            let slot = &self.readers[epoch % 2];
+            slot.fetch_add(1, Ordering::SeqCst);
+            if self.epoch.load(Ordering::SeqCst) == epoch {
+                break ReaderSlot(slot);
+            }
+            slot.fetch_sub(1, Ordering::SeqCst); // a writer flipped the epoch in the meantime, so register again
+        };
+        // the slot is released on drop, so a panicking clone cannot leave writers waiting forever
+        unsafe { (*self.ptr.load(Ordering::SeqCst)).clone() }
+    }
+    /// Replaces the current value, dropping the old one.
+    pub fn store(&self, value: OurString<T, N>) {
+        drop(self.swap(value));
+    }
+    /// Replaces the current value and returns the old one.
+    pub fn swap(&self, value: OurString<T, N>) -> OurString<T, N> {
+        let _lock = self.lock();
+        unsafe { self.replace(value) }
+    }
+    /// Replaces the current value with `new` if it is equal to `current`.
+    ///
+    /// On success, returns the old value; otherwise returns the current value (and drops `new`).
+    pub fn compare_and_swap(&self, current: &str, new: OurString<T, N>) -> Result<OurString<T, N>, OurString<T, N>> where T: Clone {
+        let _lock = self.lock();
+        // only writers reclaim values, so holding the lock keeps the current value alive
+        let value = unsafe { &*self.ptr.load(Ordering::SeqCst) };
+        if value.as_str() != current {
+            return Err(value.clone());
+        }
+        Ok(unsafe { self.replace(new) })
+    }
+    fn lock(&self) -> WriterLock<'_> {
+        while self.writer.compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed).is_err() {
+            core::hint::spin_loop();
+        }
+        WriterLock(&self.writer)
+    }
+    /// Publishes a new value and reclaims the old one once no readers can observe it.
+    ///
+    /// # Safety
+    ///
+    /// The writer lock must be held.
+    unsafe fn replace(&self, value: OurString<T, N>) -> OurString<T, N> {
+        let old = self.ptr.swap(Box::into_raw(Box::new(value)), Ordering::SeqCst);
+        let epoch = self.epoch.fetch_add(1, Ordering::SeqCst);
+        // new readers register in the other slot, so this cannot be starved by a constant stream of readers
+        while self.readers[epoch % 2].load(Ordering::SeqCst) != 0 {
+            core::hint::spin_loop();
+        }
+        unsafe { *Box::from_raw(old) }
+    }
+}
+
+struct ReaderSlot<'a>(&'a AtomicUsize);
+
+impl Drop for ReaderSlot<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+struct WriterLock<'a>(&'a AtomicBool);
+
+impl Drop for WriterLock<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+impl<T: Comrade, const N: usize> Drop for AtomicOurString<T, N> {
+    fn drop(&mut self) {
+        unsafe { drop(Box::from_raw(*self.ptr.get_mut())) }
+    }
+}
+
+impl<T: Comrade, const N: usize> From<OurString<T, N>> for AtomicOurString<T, N> {
+    fn from(value: OurString<T, N>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: Comrade, const N: usize> Default for AtomicOurString<T, N> {
+    fn default() -> Self {
+        Self::new(OurString::new())
+    }
+}
+
+impl<T: Comrade + Clone, const N: usize> Debug for AtomicOurString<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AtomicOurString").field(&self.load()).finish()
+    }
+}
+
+unsafe impl<T: Comrade + Send, const N: usize> Send for AtomicOurString<T, N> {}
+// loading clones the value on other threads, so both are needed
+unsafe impl<T: Comrade + Send + Sync, const N: usize> Sync for AtomicOurString<T, N> {}
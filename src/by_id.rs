@@ -0,0 +1,89 @@
+use core::fmt::{self, Debug};
+use core::ops::Deref;
+use core::hash::{Hash, Hasher};
+
+use crate::{Comrade, OurSlice, OurString};
+
+/// A wrapper which compares and hashes an [`OurSlice`] (or [`OurBytes`](crate::OurBytes)) or [`OurString`] by identity rather than by content.
+///
+/// Values stored by shared handles are only equal if they share the same content (see [`OurSlice::ptr_eq`]), even if other values have equal content.
+/// Inlined values have no identity, so they are compared by content (but are never equal to values stored by shared handles).
+///
+/// This is useful for graph algorithms and caches which are keyed on specific shared values rather than their content.
+#[derive(Clone, Copy, Default)]
+pub struct ById<T>(pub T);
+
+impl<T> ById<T> {
+    /// Extracts the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for ById<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> From<T> for ById<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: Debug> Debug for ById<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ById").field(&self.0).finish()
+    }
+}
+
+fn id_hash<E: Copy + Hash, T: Comrade<E>, const N: usize, H: Hasher>(value: &OurSlice<E, T, N>, state: &mut H) {
+    match value.as_comrade() {
+        Some(content) => {
+            state.write_u8(1);
+            content.identity().hash(state);
+        }
+        None => {
+            state.write_u8(0);
+            value.as_slice().hash(state);
+        }
+    }
+}
+
+fn id_eq<E: Copy + PartialEq, T: Comrade<E>, const N: usize>(a: &OurSlice<E, T, N>, b: &OurSlice<E, T, N>) -> bool {
+    match (a.as_comrade(), b.as_comrade()) {
+        (Some(a), Some(b)) => a.ptr_eq(b),
+        (None, None) => a.as_slice() == b.as_slice(),
+        _ => false,
+    }
+}
+
+impl<E: Copy + Hash, T: Comrade<E>, const N: usize> Hash for ById<OurSlice<E, T, N>> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        id_hash(&self.0, state)
+    }
+}
+
+impl<E: Copy + PartialEq, T: Comrade<E>, const N: usize> PartialEq for ById<OurSlice<E, T, N>> {
+    fn eq(&self, other: &Self) -> bool {
+        id_eq(&self.0, &other.0)
+    }
+}
+
+impl<E: Copy + Eq, T: Comrade<E>, const N: usize> Eq for ById<OurSlice<E, T, N>> {}
+
+impl<T: Comrade, const N: usize> Hash for ById<OurString<T, N>> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        id_hash(self.0.as_bytes_container(), state)
+    }
+}
+
+impl<T: Comrade, const N: usize> PartialEq for ById<OurString<T, N>> {
+    fn eq(&self, other: &Self) -> bool {
+        id_eq(self.0.as_bytes_container(), other.0.as_bytes_container())
+    }
+}
+
+impl<T: Comrade, const N: usize> Eq for ById<OurString<T, N>> {}
@@ -1,67 +1,266 @@
 use core::cmp::{Ordering, PartialEq, Eq, PartialOrd, Ord};
 use core::fmt::{self, Debug};
+use core::mem::MaybeUninit;
 use core::borrow::Borrow;
 use core::num::NonZero;
 use core::ops::Deref;
 use core::hash::Hash;
 
-use crate::Comrade;
+use crate::{Comrade, ComradeFrom, WeakComrade};
 
-#[derive(Clone)]
-enum OurInner<T, const N: usize> {
-    Inline { len: core::num::NonZero<u8>, content: [u8; N] },
+enum OurInner<E: Copy, T, const N: usize> {
+    Inline { len: core::num::NonZero<u8>, content: [MaybeUninit<E>; N] },
     Outline { content: T },
 }
 
-/// A customizable immutable shared byte collection.
+impl<E: Copy, T: Clone, const N: usize> Clone for OurInner<E, T, N> {
+    fn clone(&self) -> Self {
+        match self {
+            // only the initialized prefix is copied, so cloning scales with the content rather than the capacity
+            OurInner::Inline { len, content } => OurInner::Inline { len: *len, content: copy_prefix(content, (!len.get()) as usize) },
+            OurInner::Outline { content } => OurInner::Outline { content: content.clone() },
+        }
+    }
+}
+
+/// Copies the first `len` elements of `src` into a new (otherwise uninitialized) array.
+fn copy_prefix<E: Copy, const N: usize>(src: &[MaybeUninit<E>], len: usize) -> [MaybeUninit<E>; N] {
+    let mut content = [const { MaybeUninit::uninit() }; N];
+    content[..len].copy_from_slice(&src[..len]);
+    content
+}
+
+/// A customizable immutable shared slice of [`Copy`] elements.
 ///
-/// Data is backed inline up to `N` bytes (max 254), or stored dynamically by (shared) [`Comrade`] `T`.
+/// Data is backed inline up to `N` elements (max 254), or stored dynamically by (shared) [`Comrade`] `T`.
 ///
-/// This type can be constructed via the [`From`] trait given either a `&[u8]` (in which case inlining is attempted but may result in a shared `T` allocation)
+/// This type can be constructed via the [`From`] trait given either a `&[E]` (in which case inlining is attempted but may result in a shared `T` allocation)
 /// or a shared handle of type `T` (in which case no inlining is used and the shared handle is simply wrapped).
 ///
 /// Because of this, it is recommended to not use the `T` constructor unless you are already sharing the value around as type `T` elsewhere.
+///
+/// Comparing values which share the same content (e.g., clones) via [`Ord`] is `O(1)`, as the elements are not compared at all (see [`ptr_eq`](Self::ptr_eq)).
+/// For [`OurBytes`] this also holds for [`PartialEq`] and [`PartialOrd`], but not for other element types, which may not have reflexive equality (e.g., floats).
 #[derive(Clone)]
-pub struct OurBytes<T: Comrade, const N: usize>(OurInner<T, N>);
+pub struct OurSlice<E: Copy, T: Comrade<E>, const N: usize>(OurInner<E, T, N>);
 
-impl<T: Comrade, const N: usize> OurBytes<T, N> {
-    /// Creates a new empty instance of [`OurBytes`] with inlined data.
+/// A customizable immutable shared byte collection.
+///
+/// This is simply an [`OurSlice`] of bytes, which is backed inline up to `N` bytes (max 254), or stored dynamically by (shared) [`Comrade`] `T`.
+pub type OurBytes<T, const N: usize> = OurSlice<u8, T, N>;
+
+impl<E: Copy, T: Comrade<E>, const N: usize> OurSlice<E, T, N> {
+    /// Creates a new empty instance of [`OurSlice`] with inlined data.
     pub const fn new() -> Self {
-        Self(OurInner::Inline { len: NonZero::<u8>::MAX, content: [0; N] })
+        Self(OurInner::Inline { len: NonZero::<u8>::MAX, content: [const { MaybeUninit::uninit() }; N] })
     }
-    /// Converts this [`OurBytes`] instance into another [`OurBytes`] type which uses the same shared type `T`.
+    /// Converts this [`OurSlice`] instance into another [`OurSlice`] type which uses the same shared type `T`.
     ///
     /// If the content of this instance is already allocated via shared handle `T`, that handle will simply be reused without inlining.
     /// Otherwise, re-inlining will be attempted, but may fail if `M < N` and result in a new shared `T` allocation.
     ///
-    /// Because of this, it is advised to minimize the use of this function (e.g., by only using one [`OurBytes`] type throughout your codebase).
-    pub fn convert<const M: usize>(self) -> OurBytes<T, M> {
+    /// Because of this, it is advised to minimize the use of this function (e.g., by only using one [`OurSlice`] type throughout your codebase).
+    /// See also [`convert_compact`](Self::convert_compact) and [`try_convert`](Self::try_convert).
+    pub fn convert<const M: usize>(self) -> OurSlice<E, T, M> {
         match self.0 {
-            OurInner::Inline { .. } => OurBytes::from(self.as_slice()),
-            OurInner::Outline { content } => OurBytes::from(content),
+            OurInner::Inline { .. } => OurSlice::from(self.as_slice()),
+            OurInner::Outline { content } => OurSlice::from_comrade(content),
+        }
+    }
+    /// Like [`convert`](Self::convert), but re-inlines content which fits in `M` elements even if it is currently stored by shared handle `T` (which is then dropped).
+    ///
+    /// This can free shared buffers which are no longer needed, but loses sharing with other handles to the same content.
+    pub fn convert_compact<const M: usize>(self) -> OurSlice<E, T, M> {
+        match fits_inline::<M>(self.len()) {
+            true => OurSlice::from(self.as_slice()),
+            false => self.convert(),
+        }
+    }
+    /// Like [`convert`](Self::convert), but fails (returning the original value) instead of making a new shared `T` allocation,
+    /// which happens if the content is inlined but does not fit in `M` elements.
+    pub fn try_convert<const M: usize>(self) -> Result<OurSlice<E, T, M>, Self> {
+        match &self.0 {
+            OurInner::Inline { .. } if !fits_inline::<M>(self.len()) => Err(self),
+            _ => Ok(self.convert()),
         }
     }
+    /// Builds inlined content in place, where `fill` is given the inline storage and returns the number of leading elements it initialized,
+    /// or [`None`] if the content does not fit (in which case [`None`] is returned).
+    ///
+    /// # Safety
+    ///
+    /// If `fill` returns `Some(len)`, the first `len` elements of the storage it was given must have been initialized.
+    pub(crate) unsafe fn try_inline_with(fill: impl FnOnce(&mut [MaybeUninit<E>]) -> Option<usize>) -> Option<Self> {
+        let mut content = [const { MaybeUninit::uninit() }; N];
+        let len = fill(&mut content[..if N < u8::MAX as usize { N } else { u8::MAX as usize - 1 }])?;
+        Some(Self(OurInner::Inline { len: NonZero::new(!(len as u8)).unwrap(), content }))
+    }
+    /// Wraps a shared handle of type `T` without inlining.
+    ///
+    /// This is equivalent to the [`From`] impl, which is only available for primitive element types (for coherence reasons).
+    pub fn from_comrade(content: T) -> Self {
+        Self(OurInner::Outline { content })
+    }
+    /// Like [`from_comrade`](Self::from_comrade), but inlines the content (and drops the handle) if it fits in `N` elements.
+    ///
+    /// The result is always in canonical form (see [`is_canonical`](Self::is_canonical)).
+    pub fn from_comrade_inlining(content: T) -> Self {
+        match fits_inline::<N>(content.as_slice().len()) {
+            true => Self::from(content.as_slice()),
+            false => Self::from_comrade(content),
+        }
+    }
+    /// Re-inlines the content (dropping the shared handle) if it is stored by shared handle `T` but fits in `N` elements.
+    ///
+    /// Afterwards, this value is in canonical form (see [`is_canonical`](Self::is_canonical)).
+    pub fn normalize(&mut self) {
+        if !self.is_canonical() {
+            *self = Self::from(self.as_slice());
+        }
+    }
+    /// Checks if this value is in canonical form, meaning that content is inlined if and only if it fits in `N` elements.
+    ///
+    /// Values constructed from slices are always canonical, but wrapping a shared handle (e.g., via [`from_comrade`](Self::from_comrade)) may not be.
+    pub fn is_canonical(&self) -> bool {
+        self.is_inline() == fits_inline::<N>(self.len())
+    }
+    /// Asserts that this value is in canonical form (see [`is_canonical`](Self::is_canonical)), but only in debug builds.
+    #[track_caller]
+    pub fn debug_assert_canonical(&self) {
+        debug_assert!(self.is_canonical(), "value is not in canonical form");
+    }
     /// Gets a shared reference to the content.
-    pub fn as_slice(&self) -> &[u8] {
+    pub fn as_slice(&self) -> &[E] {
         self
     }
+    /// Checks if the content is stored inline (rather than by shared handle `T`).
+    pub fn is_inline(&self) -> bool {
+        matches!(self.0, OurInner::Inline { .. })
+    }
+    /// Gets the max number of elements which can be stored inline, which is `N` capped at 254.
+    pub const fn inline_capacity(&self) -> usize {
+        if N < u8::MAX as usize { N } else { u8::MAX as usize - 1 }
+    }
+    /// Gets the number of strong handles to the shared content (see [`Comrade::strong_count`]).
+    ///
+    /// This is `None` if the content is inlined (and thus not shared) or if `T` does not support it.
+    pub fn strong_count(&self) -> Option<usize> {
+        self.as_comrade().and_then(T::strong_count)
+    }
+    /// Gets the number of heap bytes used by the content, which is zero if it is inlined.
+    ///
+    /// Shared content is counted in full (see [`HeapUsage`](crate::HeapUsage) for de-duplicating shared content across many values).
+    /// If `T` does not support [`Comrade::heap_size`], this is just the size of the content itself.
+    pub fn heap_size(&self) -> usize {
+        match self.as_comrade() {
+            Some(content) => content.heap_size().unwrap_or(size_of_val(content.as_slice())),
+            None => 0,
+        }
+    }
+    /// Checks if both values are stored by shared handles to the same content (see [`Comrade::ptr_eq`]).
+    ///
+    /// Inlined content is never shared, so this is always `false` if either value is inlined.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (OurInner::Outline { content: a }, OurInner::Outline { content: b }) => a.ptr_eq(b),
+            _ => false,
+        }
+    }
+    /// Gets a shared reference to the shared handle `T`, or `None` if the content is inlined.
+    pub fn as_comrade(&self) -> Option<&T> {
+        match &self.0 {
+            OurInner::Inline { .. } => None,
+            OurInner::Outline { content } => Some(content),
+        }
+    }
+    /// Extracts the shared handle `T` without copying, or fails (returning the original value) if the content is inlined.
+    pub fn into_comrade(self) -> Result<T, Self> {
+        match self.0 {
+            OurInner::Inline { .. } => Err(self),
+            OurInner::Outline { content } => Ok(content),
+        }
+    }
+    /// Extracts the shared handle `T`, copying inlined content into a new shared `T` allocation if needed.
+    pub fn into_comrade_or_alloc(self) -> T {
+        match self.0 {
+            OurInner::Inline { .. } => T::from_slice(self.as_slice()),
+            OurInner::Outline { content } => content,
+        }
+    }
+}
+
+impl<T: Comrade, const N: usize> OurBytes<T, N> {
+    /// Converts this [`OurBytes`] instance into another [`OurBytes`] type which uses a different shared type `U`.
+    ///
+    /// If the content of this instance is already allocated via shared handle `T`, it will be converted via [`ComradeFrom`] (which may reuse the allocation) without inlining.
+    /// Otherwise, re-inlining will be attempted, but may fail if `M < N` and result in a new shared `U` allocation.
+    pub fn convert_comrade<U: ComradeFrom<T>, const M: usize>(self) -> OurBytes<U, M> {
+        match self.0 {
+            OurInner::Inline { .. } => OurBytes::from(self.as_slice()),
+            OurInner::Outline { content } => OurBytes::from(U::comrade_from(content)),
+        }
+    }
+}
+
+impl<E: Copy, T: WeakComrade<E>, const N: usize> OurSlice<E, T, N> {
+    /// Creates a new [`OurWeakSlice`] handle to the same content.
+    ///
+    /// Inlined content is simply copied into the weak handle, so upgrading it will always succeed.
+    pub fn downgrade(&self) -> OurWeakSlice<E, T, N> {
+        match &self.0 {
+            OurInner::Inline { len, content } => OurWeakSlice(OurInner::Inline { len: *len, content: copy_prefix(content, (!len.get()) as usize) }),
+            OurInner::Outline { content } => OurWeakSlice(OurInner::Outline { content: content.downgrade() }),
+        }
+    }
+}
+
+/// A weak handle to the content of an [`OurSlice`] which does not keep shared content alive.
+///
+/// This type can be constructed via [`OurSlice::downgrade`].
+#[derive(Clone)]
+pub struct OurWeakSlice<E: Copy, T: WeakComrade<E>, const N: usize>(OurInner<E, T::Weak, N>);
+
+/// A weak handle to the content of an [`OurBytes`] which does not keep shared content alive.
+pub type OurWeakBytes<T, const N: usize> = OurWeakSlice<u8, T, N>;
+
+impl<E: Copy, T: WeakComrade<E>, const N: usize> OurWeakSlice<E, T, N> {
+    /// Attempts to upgrade this handle into an [`OurSlice`], which fails if the shared content has already been dropped.
+    ///
+    /// This always succeeds for inlined content.
+    pub fn upgrade(&self) -> Option<OurSlice<E, T, N>> {
+        match &self.0 {
+            OurInner::Inline { len, content } => Some(OurSlice(OurInner::Inline { len: *len, content: copy_prefix(content, (!len.get()) as usize) })),
+            OurInner::Outline { content } => T::upgrade(content).map(OurSlice::from_comrade),
+        }
+    }
+}
+
+impl<E: Copy, T: WeakComrade<E>, const N: usize> Debug for OurWeakSlice<E, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(Weak)")
+    }
 }
 
-impl<T: Comrade, const N: usize> Deref for OurBytes<T, N> {
-    type Target = [u8];
+impl<E: Copy, T: Comrade<E>, const N: usize> Deref for OurSlice<E, T, N> {
+    type Target = [E];
     fn deref(&self) -> &Self::Target {
         match &self.0 {
-            OurInner::Inline { len, content } => &content[..(!len.get()) as usize],
+            OurInner::Inline { len, content } => unsafe { core::slice::from_raw_parts(content.as_ptr() as *const E, (!len.get()) as usize) },
             OurInner::Outline { content } => content.as_slice(),
         }
     }
 }
 
-impl<T: Comrade, const N: usize> From<&[u8]> for OurBytes<T, N> {
-    fn from(value: &[u8]) -> Self {
-        if value.len() <= N && value.len() < u8::MAX as usize {
-            let mut content = [0; N];
-            content[..value.len()].copy_from_slice(value);
+/// Checks if content with the given length can be inlined with a max inlining size of `N`.
+const fn fits_inline<const N: usize>(len: usize) -> bool {
+    len <= N && len < u8::MAX as usize
+}
+
+impl<E: Copy, T: Comrade<E>, const N: usize> From<&[E]> for OurSlice<E, T, N> {
+    fn from(value: &[E]) -> Self {
+        if fits_inline::<N>(value.len()) {
+            let mut content = [const { MaybeUninit::uninit() }; N];
+            unsafe { core::ptr::copy_nonoverlapping(value.as_ptr(), content.as_mut_ptr() as *mut E, value.len()); }
             Self(OurInner::Inline { len: NonZero::new(!(value.len() as u8)).unwrap(), content })
         } else {
             Self(OurInner::Outline { content: T::from_slice(value) })
@@ -69,70 +268,92 @@ impl<T: Comrade, const N: usize> From<&[u8]> for OurBytes<T, N> {
     }
 }
 
-impl<T: Comrade, const N: usize> From<T> for OurBytes<T, N> {
-    fn from(content: T) -> Self {
-        Self(OurInner::Outline { content })
-    }
+macro_rules! impl_from_comrade {
+    ($($e:ty),*) => {$(
+        impl<T: Comrade<$e>, const N: usize> From<T> for OurSlice<$e, T, N> {
+            fn from(content: T) -> Self {
+                Self::from_comrade(content)
+            }
+        }
+    )*};
 }
+impl_from_comrade! { u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, char, bool }
 
-impl<T: Comrade, const N: usize> Default for OurBytes<T, N> {
+impl<E: Copy, T: Comrade<E>, const N: usize> Default for OurSlice<E, T, N> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<T: Comrade, const N: usize> AsRef<[u8]> for OurBytes<T, N> {
-    fn as_ref(&self) -> &[u8] {
+impl<E: Copy, T: Comrade<E>, const N: usize> AsRef<[E]> for OurSlice<E, T, N> {
+    fn as_ref(&self) -> &[E] {
         self
     }
 }
 
-impl<T: Comrade, const N: usize> Borrow<[u8]> for OurBytes<T, N> {
-    fn borrow(&self) -> &[u8] {
+impl<E: Copy, T: Comrade<E>, const N: usize> Borrow<[E]> for OurSlice<E, T, N> {
+    fn borrow(&self) -> &[E] {
         self
     }
 }
 
-impl<T: Comrade, const N: usize> Debug for OurBytes<T, N> {
+impl<E: Copy + Debug, T: Comrade<E>, const N: usize> Debug for OurSlice<E, T, N> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        <[u8] as Debug>::fmt(&**self, f)
+        <[E] as Debug>::fmt(&**self, f)
     }
 }
 
-impl<T: Comrade, const N: usize> Hash for OurBytes<T, N> {
+impl<E: Copy + Hash, T: Comrade<E>, const N: usize> Hash for OurSlice<E, T, N> {
     fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
         (**self).hash(state)
     }
 }
 
-impl<U: Deref<Target = [u8]>, T: Comrade, const N: usize> PartialEq<U> for OurBytes<T, N> {
+/// Checks if both slices are the exact same memory, in which case they are trivially equal.
+///
+/// This only holds if `E` has reflexive equality, which is not the case for, e.g., floats (NaN),
+/// and we can't require `E: Eq` for [`PartialEq`], so this only applies to bytes.
+fn same_bytes<E>(a: &[E], b: &[E]) -> bool {
+    core::any::type_name::<E>() == "u8" && core::ptr::eq(a, b)
+}
+
+impl<U: Deref<Target = [E]>, E: Copy + PartialEq, T: Comrade<E>, const N: usize> PartialEq<U> for OurSlice<E, T, N> {
     fn eq(&self, other: &U) -> bool {
-        (**self).eq(&**other)
+        same_bytes(self, other) || (**self).eq(&**other)
     }
 }
 
-impl<T: Comrade, const N: usize> PartialEq<OurBytes<T, N>> for &[u8] {
-    fn eq(&self, other: &OurBytes<T, N>) -> bool {
-        (**self).eq(&**other)
+impl<E: Copy + PartialEq, T: Comrade<E>, const N: usize> PartialEq<OurSlice<E, T, N>> for &[E] {
+    fn eq(&self, other: &OurSlice<E, T, N>) -> bool {
+        same_bytes(self, other) || (**self).eq(&**other)
     }
 }
 
-impl<T: Comrade, const N: usize> Eq for OurBytes<T, N> {}
+impl<E: Copy + Eq, T: Comrade<E>, const N: usize> Eq for OurSlice<E, T, N> {}
 
-impl<U: Deref<Target = [u8]>, T: Comrade, const N: usize> PartialOrd<U> for OurBytes<T, N> {
+impl<U: Deref<Target = [E]>, E: Copy + PartialOrd, T: Comrade<E>, const N: usize> PartialOrd<U> for OurSlice<E, T, N> {
     fn partial_cmp(&self, other: &U) -> Option<Ordering> {
+        if same_bytes(self, other) {
+            return Some(Ordering::Equal);
+        }
         (**self).partial_cmp(&**other)
     }
 }
 
-impl<T: Comrade, const N: usize> PartialOrd<OurBytes<T, N>> for &[u8] {
-    fn partial_cmp(&self, other: &OurBytes<T, N>) -> Option<Ordering> {
+impl<E: Copy + PartialOrd, T: Comrade<E>, const N: usize> PartialOrd<OurSlice<E, T, N>> for &[E] {
+    fn partial_cmp(&self, other: &OurSlice<E, T, N>) -> Option<Ordering> {
+        if same_bytes(self, other) {
+            return Some(Ordering::Equal);
+        }
         (**self).partial_cmp(&**other)
     }
 }
 
-impl<T: Comrade, const N: usize> Ord for OurBytes<T, N> {
+impl<E: Copy + Ord, T: Comrade<E>, const N: usize> Ord for OurSlice<E, T, N> {
     fn cmp(&self, other: &Self) -> Ordering {
+        if self.ptr_eq(other) {
+            return Ordering::Equal;
+        }
         (**self).cmp(&**other)
     }
 }
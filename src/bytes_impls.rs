@@ -0,0 +1,90 @@
+use core::any::Any;
+
+use ::bytes::{Buf, Bytes};
+
+use crate::{Comrade, OurBytes, OurString};
+
+impl Comrade for Bytes {
+    fn from_slice(s: &[u8]) -> Self {
+        Bytes::copy_from_slice(s)
+    }
+    fn as_slice(&self) -> &[u8] {
+        self
+    }
+}
+
+/// A [`Buf`] cursor over the content of an [`OurBytes`] or [`OurString`].
+///
+/// This type can be constructed via the [`From`] trait given either an [`OurBytes`] or [`OurString`].
+#[derive(Clone, Debug)]
+pub struct OurBuf<T: Comrade, const N: usize> {
+    value: OurBytes<T, N>,
+    pos: usize,
+}
+
+impl<T: Comrade, const N: usize> OurBuf<T, N> {
+    /// Extracts the underlying (full) bytes container, regardless of the current position.
+    pub fn into_inner(self) -> OurBytes<T, N> {
+        self.value
+    }
+}
+
+impl<T: Comrade, const N: usize> From<OurBytes<T, N>> for OurBuf<T, N> {
+    fn from(value: OurBytes<T, N>) -> Self {
+        Self { value, pos: 0 }
+    }
+}
+
+impl<T: Comrade, const N: usize> From<OurString<T, N>> for OurBuf<T, N> {
+    fn from(value: OurString<T, N>) -> Self {
+        Self::from(value.into_bytes())
+    }
+}
+
+/// [`copy_to_bytes`](Buf::copy_to_bytes) slices the shared handle without copying if it is a [`Bytes`] or [`ArcBytes`](crate::comrades::ArcBytes), and copies otherwise.
+impl<T: Comrade + 'static, const N: usize> Buf for OurBuf<T, N> {
+    fn remaining(&self) -> usize {
+        self.value.len() - self.pos
+    }
+    fn chunk(&self) -> &[u8] {
+        &self.value[self.pos..]
+    }
+    fn advance(&mut self, cnt: usize) {
+        assert!(cnt <= self.remaining(), "cannot advance past the end of the buffer");
+        self.pos += cnt;
+    }
+    fn copy_to_bytes(&mut self, len: usize) -> Bytes {
+        assert!(len <= self.remaining(), "cannot advance past the end of the buffer");
+        let range = self.pos..self.pos + len;
+        self.pos += len;
+        if let Some(content) = self.value.as_comrade().map(|x| x as &dyn Any) {
+            if let Some(content) = content.downcast_ref::<Bytes>() {
+                return content.slice(range);
+            }
+            #[cfg(target_has_atomic = "ptr")]
+            if let Some(content) = content.downcast_ref::<crate::comrades::ArcBytes>() {
+                return Bytes::from_owner(content.clone()).slice(range);
+            }
+        }
+        Bytes::copy_from_slice(&self.value[range])
+    }
+}
+
+/// Shared content is wrapped without copying, while inlined content is copied into a new [`Bytes`].
+///
+/// Note that the reverse conversion is only zero-copy for [`OurBytes<Bytes, N>`](OurBytes), via [`From<T>`](From).
+impl<T: Comrade + Send + 'static, const N: usize> From<OurBytes<T, N>> for Bytes {
+    fn from(value: OurBytes<T, N>) -> Self {
+        match value.as_comrade() {
+            Some(_) => Bytes::from_owner(value),
+            None => Bytes::copy_from_slice(&value),
+        }
+    }
+}
+
+/// Shared content is wrapped without copying, while inlined content is copied into a new [`Bytes`].
+impl<T: Comrade + Send + 'static, const N: usize> From<OurString<T, N>> for Bytes {
+    fn from(value: OurString<T, N>) -> Self {
+        Bytes::from(value.into_bytes())
+    }
+}
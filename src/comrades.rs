@@ -1,65 +1,522 @@
 //! Specialized [`Comrade`](crate::Comrade) types.
+//!
+//! # Weak handles
+//!
+//! The default [`RcBytes`] and [`ArcBytes`] (and [`RcSlice`] and [`ArcSlice`]) do **not** support weak handles.
+//! Their header only holds a strong count and a length, so that the common case does not pay for a weak count,
+//! and so that a handle is known to be unique (e.g., for in-place conversion between them) from the strong count alone.
+//!
+//! If you need [`downgrade`](crate::OurString::downgrade), use the `W`-suffixed strong types instead:
+//!
+//! | strong type      | weak handle     |
+//! |------------------|-----------------|
+//! | [`RcBytesW`]     | [`WeakRcBytes`] |
+//! | [`ArcBytesW`]    | [`WeakArcBytes`] |
+//! | [`RcSliceW`]     | [`WeakRcSlice`] |
+//! | [`ArcSliceW`]    | [`WeakArcSlice`] |
+//!
+//! These hold an extra weak count in their header (one more word per allocation).
 
 use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
 use core::borrow::Borrow;
 use core::cmp::Ordering;
 use core::ptr::NonNull;
+use core::alloc::Layout;
 use core::cell::Cell;
 use core::ops::Deref;
 use core::fmt;
 
 const ALIGN: usize = align_of::<usize>();
 
+/// Max reference count before we abort, mirroring [`Arc`](alloc::sync::Arc).
+/// Using `isize::MAX` (rather than `usize::MAX`) leaves plenty of headroom for racing threads to not wrap around before aborting.
+const MAX_REFCOUNT: usize = isize::MAX as usize;
+/// Max reference count for compact comrades, with the same headroom as [`MAX_REFCOUNT`].
+const MAX_REFCOUNT32: u32 = i32::MAX as u32;
+
+/// Aborts the process, even in `no_std` environments (by panicking while panicking).
+#[cold]
+fn abort() -> ! {
+    struct Abort;
+    impl Drop for Abort {
+        fn drop(&mut self) {
+            panic!("reference count overflow");
+        }
+    }
+    let _abort = Abort;
+    panic!("reference count overflow");
+}
+
+/// Gets the layout for a buffer with the given header size and content length (in elements), panicking if it is too large.
+fn layout<E, const A: usize>(header: usize, len: usize) -> Layout {
+    const { assert!(A.is_power_of_two(), "alignment must be a power of two") }
+    match len.checked_mul(size_of::<E>()).and_then(|size| size.checked_add(data::<E, A>(header))).map(|size| Layout::from_size_align(size, align::<E, A>())) {
+        Some(Ok(layout)) => layout,
+        _ => panic!("capacity overflow"),
+    }
+}
+
+// header layout: [strong count][content length][padding][content...]
+const HEADER: usize = 2 * ALIGN;
+// weak header layout: [strong count][weak count][content length][padding][content...]
+const WEAK: usize = ALIGN;
+const WEAK_HEADER: usize = 3 * ALIGN;
+
+/// Gets the alignment of the content in a buffer holding elements of type `E` with requested alignment `A`.
+const fn content_align<E, const A: usize>() -> usize {
+    if align_of::<E>() > A { align_of::<E>() } else { A }
+}
+/// Gets the alignment of a buffer holding elements of type `E` with requested alignment `A`.
+const fn align<E, const A: usize>() -> usize {
+    if content_align::<E, A>() > ALIGN { content_align::<E, A>() } else { ALIGN }
+}
+/// Gets the offset of the content in a buffer holding elements of type `E` (the header rounded up to the content alignment).
+const fn data<E, const A: usize>(header: usize) -> usize {
+    (header + content_align::<E, A>() - 1) & !(content_align::<E, A>() - 1)
+}
+
 trait Counter {
     fn increment(&self);
     fn decrement(&self) -> usize;
+    /// Increments the count if it is non-zero, returning `true` on success.
+    fn upgrade(&self) -> bool;
+    /// Sets the count to zero if it is exactly one, returning `true` on success.
+    fn take_unique(&self) -> bool;
+    fn load(&self) -> usize;
 }
 
-impl Counter for Cell<usize> {
-    fn increment(&self) {
-        self.set(self.get() + 1);
-    }
-    fn decrement(&self) -> usize {
-        let res = self.get() - 1;
-        self.set(res);
-        res
-    }
+macro_rules! impl_counter {
+    (atomic $(#[$a:meta])* $atomic:ty : $max:expr) => {
+        $(#[$a])*
+        impl Counter for $atomic {
+            fn increment(&self) {
+                if self.fetch_add(1, core::sync::atomic::Ordering::Relaxed) >= $max {
+                    abort();
+                }
+            }
+            fn decrement(&self) -> usize {
+                (self.fetch_sub(1, core::sync::atomic::Ordering::AcqRel) - 1) as usize
+            }
+            fn upgrade(&self) -> bool {
+                self.fetch_update(core::sync::atomic::Ordering::Acquire, core::sync::atomic::Ordering::Relaxed, |count| match count {
+                    0 => None,
+                    _ if count >= $max => abort(),
+                    _ => Some(count + 1),
+                }).is_ok()
+            }
+            fn take_unique(&self) -> bool {
+                self.compare_exchange(1, 0, core::sync::atomic::Ordering::Acquire, core::sync::atomic::Ordering::Relaxed).is_ok()
+            }
+            fn load(&self) -> usize {
+                self.load(core::sync::atomic::Ordering::Acquire) as usize
+            }
+        }
+    };
+    ($($cell:ty, $(#[$a:meta])* $atomic:ty : $max:expr),*) => {$(
+        impl Counter for $cell {
+            fn increment(&self) {
+                let count = self.get();
+                if count >= $max {
+                    abort();
+                }
+                self.set(count + 1);
+            }
+            fn decrement(&self) -> usize {
+                let res = self.get() - 1;
+                self.set(res);
+                res as usize
+            }
+            fn upgrade(&self) -> bool {
+                match self.get() {
+                    0 => false,
+                    _ => {
+                        self.increment();
+                        true
+                    }
+                }
+            }
+            fn take_unique(&self) -> bool {
+                match self.get() {
+                    1 => {
+                        self.set(0);
+                        true
+                    }
+                    _ => false,
+                }
+            }
+            fn load(&self) -> usize {
+                self.get() as usize
+            }
+        }
+
+        impl_counter!(atomic $(#[$a])* $atomic : $max);
+    )*};
+}
+impl_counter! {
+    Cell<usize>, #[cfg(target_has_atomic = "ptr")] core::sync::atomic::AtomicUsize : MAX_REFCOUNT,
+    Cell<u32>, #[cfg(target_has_atomic = "32")] core::sync::atomic::AtomicU32 : MAX_REFCOUNT32
+}
+#[cfg(all(test, loom))]
+impl_counter!(atomic loom::sync::atomic::AtomicUsize : MAX_REFCOUNT);
+
+/// Implements the common traits of a shared slice type `$ty` with elements `$e`, given [`Deref`] and `From<&[$e]>`.
+macro_rules! impl_slice_traits {
+    ([$($g:tt)*] $ty:ty : $e:ty) => {
+        impl<$($g)*> Default for $ty {
+            fn default() -> Self {
+                Self::from([].as_slice())
+            }
+        }
+
+        impl<$($g)*> AsRef<[$e]> for $ty {
+            fn as_ref(&self) -> &[$e] {
+                self
+            }
+        }
+
+        impl<$($g)*> Borrow<[$e]> for $ty {
+            fn borrow(&self) -> &[$e] {
+                self
+            }
+        }
+
+        impl<$($g)*> Hash for $ty where $e: Hash {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                (**self).hash(state);
+            }
+        }
+
+        impl<$($g)*> fmt::Debug for $ty where $e: fmt::Debug {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{:?}", &**self)
+            }
+        }
+
+        impl<$($g)* T: AsRef<[$e]>> PartialEq<T> for $ty where $e: PartialEq {
+            fn eq(&self, other: &T) -> bool {
+                (**self).eq(other.as_ref())
+            }
+        }
+
+        impl<$($g)*> Eq for $ty where $e: Eq {}
+
+        impl<$($g)* T: AsRef<[$e]>> PartialOrd<T> for $ty where $e: PartialOrd {
+            fn partial_cmp(&self, other: &T) -> Option<Ordering> {
+                (**self).partial_cmp(other.as_ref())
+            }
+        }
+
+        impl<$($g)*> Ord for $ty where $e: Ord {
+            fn cmp(&self, other: &Self) -> Ordering {
+                (**self).cmp(&**other)
+            }
+        }
+
+        impl<$($g)*> crate::Comrade<$e> for $ty {
+            fn from_slice(s: &[$e]) -> Self {
+                s.into()
+            }
+            fn as_slice(&self) -> &[$e] {
+                self
+            }
+            fn strong_count(&self) -> Option<usize> {
+                Some(<$ty>::strong_count(self))
+            }
+            fn heap_size(&self) -> Option<usize> {
+                Some(<$ty>::allocation_size(self))
+            }
+        }
+    };
+}
+
+macro_rules! make_comrade {
+    ($(#[$m:meta])? $vis:vis struct $name:ident : $counter:ty) => {
+        $(#[$m])?
+        ///
+        /// The content is aligned to both `E` and `A` (which must be a power of two), e.g., for SIMD scanning or reinterpreting the content as wider integers.
+        $vis struct $name<E: Copy = u8, const A: usize = 1>(NonNull<u8>, PhantomData<E>);
+
+        impl<E: Copy, const A: usize> $name<E, A> {
+            const LEN: usize = ALIGN;
+            const HEADER: usize = HEADER;
+            unsafe fn init_header(_ptr: *mut u8) {}
+            /// Releases a strong reference, deallocating the buffer if it was the last one.
+            unsafe fn release(ptr: NonNull<u8>) {
+                unsafe {
+                    if Self::strong(ptr).decrement() == 0 {
+                        Self::dealloc(ptr);
+                    }
+                }
+            }
+        }
+
+        make_comrade!(@common $name : $counter);
+    };
+    ($(#[$m:meta])? $vis:vis struct $name:ident / $weak:ident : $counter:ty) => {
+        $(#[$m])?
+        ///
+        /// Weak handles which do not keep the content alive can be made via [`downgrade`](Self::downgrade).
+        /// Supporting this costs an extra weak count (one word) in the header of every allocation, even if it is never downgraded.
+        ///
+        /// The content is aligned to both `E` and `A` (which must be a power of two), e.g., for SIMD scanning or reinterpreting the content as wider integers.
+        $vis struct $name<E: Copy = u8, const A: usize = 1>(NonNull<u8>, PhantomData<E>);
+
+        #[doc = concat!("A weak handle to a [`", stringify!($name), "`] which does not keep the content alive.")]
+        $vis struct $weak<E: Copy = u8, const A: usize = 1>(NonNull<u8>, PhantomData<E>);
+
+        impl<E: Copy, const A: usize> $name<E, A> {
+            const LEN: usize = 2 * ALIGN;
+            const HEADER: usize = WEAK_HEADER;
+            unsafe fn init_header(ptr: *mut u8) {
+                unsafe { (ptr.add(WEAK) as *mut $counter).write(<$counter>::new(1)); } // all strong handles share one weak handle
+            }
+            /// Releases a strong reference, releasing the shared weak reference if it was the last one.
+            unsafe fn release(ptr: NonNull<u8>) {
+                unsafe {
+                    if Self::strong(ptr).decrement() == 0 {
+                        Self::release_weak(ptr);
+                    }
+                }
+            }
+            #[doc = concat!("Creates a new [`", stringify!($weak), "`] handle to the same content.")]
+            pub fn downgrade(this: &Self) -> $weak<E, A> {
+                unsafe { Self::weak(this.0).increment(); }
+                $weak(this.0, PhantomData)
+            }
+            unsafe fn weak<'a>(ptr: NonNull<u8>) -> &'a $counter {
+                unsafe { &*(ptr.as_ptr().add(WEAK) as *const $counter) }
+            }
+            /// Releases a weak reference, deallocating the buffer if it was the last one.
+            unsafe fn release_weak(ptr: NonNull<u8>) {
+                unsafe {
+                    if Self::weak(ptr).decrement() == 0 {
+                        Self::dealloc(ptr);
+                    }
+                }
+            }
+        }
+
+        impl<E: Copy, const A: usize> $weak<E, A> {
+            #[doc = concat!("Attempts to upgrade this handle into a [`", stringify!($name), "`], which fails if the content has already been dropped.")]
+            pub fn upgrade(&self) -> Option<$name<E, A>> {
+                unsafe { $name::<E, A>::strong(self.0).upgrade().then(|| $name(self.0, PhantomData)) }
+            }
+        }
+
+        impl<E: Copy, const A: usize> Clone for $weak<E, A> {
+            fn clone(&self) -> Self {
+                unsafe { $name::<E, A>::weak(self.0).increment(); }
+                Self(self.0, PhantomData)
+            }
+        }
+
+        impl<E: Copy, const A: usize> Drop for $weak<E, A> {
+            fn drop(&mut self) {
+                unsafe { $name::<E, A>::release_weak(self.0); }
+            }
+        }
+
+        impl<E: Copy, const A: usize> fmt::Debug for $weak<E, A> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "(Weak)")
+            }
+        }
+
+        impl<E: Copy, const A: usize> crate::WeakComrade<E> for $name<E, A> {
+            type Weak = $weak<E, A>;
+            fn downgrade(&self) -> Self::Weak {
+                Self::downgrade(self)
+            }
+            fn upgrade(weak: &Self::Weak) -> Option<Self> {
+                weak.upgrade()
+            }
+        }
+
+        make_comrade!(@common $name : $counter);
+    };
+    (@common $name:ident : $counter:ty) => {
+        impl<E: Copy, const A: usize> $name<E, A> {
+            /// Gets the number of strong handles to the content.
+            pub fn strong_count(this: &Self) -> usize {
+                unsafe { Counter::load(Self::strong(this.0)) }
+            }
+            fn allocation_size(this: &Self) -> usize {
+                unsafe { data::<E, A>(Self::HEADER) + Self::content_len(this.0) * size_of::<E>() }
+            }
+            unsafe fn strong<'a>(ptr: NonNull<u8>) -> &'a $counter {
+                unsafe { &*(ptr.as_ptr() as *const $counter) }
+            }
+            unsafe fn content_len(ptr: NonNull<u8>) -> usize {
+                unsafe { *(ptr.as_ptr().add(Self::LEN) as *const usize) }
+            }
+            unsafe fn content<'a>(ptr: NonNull<u8>) -> &'a [E] {
+                unsafe { core::slice::from_raw_parts(ptr.as_ptr().add(data::<E, A>(Self::HEADER)) as *const E, Self::content_len(ptr)) }
+            }
+            unsafe fn dealloc(ptr: NonNull<u8>) {
+                unsafe { alloc::alloc::dealloc(ptr.as_ptr(), Layout::from_size_align_unchecked(data::<E, A>(Self::HEADER) + Self::content_len(ptr) * size_of::<E>(), align::<E, A>())); }
+            }
+        }
+
+        impl<E: Copy, const A: usize> From<&[E]> for $name<E, A> {
+            fn from(value: &[E]) -> Self {
+                debug_assert!(align_of::<$counter>() == ALIGN && size_of::<$counter>() == ALIGN);
+
+                let layout = layout::<E, A>(Self::HEADER, value.len());
+                unsafe {
+                    let ptr = alloc::alloc::alloc(layout);
+                    if ptr.is_null() {
+                        alloc::alloc::handle_alloc_error(layout);
+                    }
+                    (ptr as *mut $counter).write(<$counter>::new(1));
+                    Self::init_header(ptr);
+                    (ptr.add(Self::LEN) as *mut usize).write(value.len());
+                    (ptr.add(data::<E, A>(Self::HEADER)) as *mut E).copy_from_nonoverlapping(value.as_ptr(), value.len());
+                    Self(NonNull::new_unchecked(ptr), PhantomData)
+                }
+            }
+        }
+
+        impl<E: Copy, const A: usize> Clone for $name<E, A> {
+            fn clone(&self) -> Self {
+                unsafe { Self::strong(self.0).increment(); }
+                Self(self.0, PhantomData)
+            }
+        }
+
+        impl<E: Copy, const A: usize> Drop for $name<E, A> {
+            fn drop(&mut self) {
+                unsafe { Self::release(self.0); }
+            }
+        }
+
+        impl<E: Copy, const A: usize> Deref for $name<E, A> {
+            type Target = [E];
+            fn deref(&self) -> &Self::Target {
+                unsafe { Self::content(self.0) }
+            }
+        }
+
+        impl_slice_traits!([E: Copy, const A: usize,] $name<E, A> : E);
+
+        unsafe impl<const A: usize> crate::ThinComrade for $name<u8, A> {
+            fn into_raw(self) -> NonNull<u8> {
+                core::mem::ManuallyDrop::new(self).0
+            }
+            unsafe fn from_raw(ptr: NonNull<u8>) -> Self {
+                Self(ptr, PhantomData)
+            }
+        }
+    };
 }
+make_comrade!(#[doc = "Basically `Rc<[E]>` but only takes up half the stack space."] pub struct RcSlice : Cell<usize>);
+#[cfg(target_has_atomic = "ptr")]
+make_comrade!(#[doc = "Basically `Arc<[E]>` but only takes up half the stack space."] pub struct ArcSlice : core::sync::atomic::AtomicUsize);
+make_comrade!(#[doc = "Like [`RcSlice`], but with support for weak handles."] pub struct RcSliceW / WeakRcSlice : Cell<usize>);
+#[cfg(target_has_atomic = "ptr")]
+make_comrade!(#[doc = "Like [`ArcSlice`], but with support for weak handles."] pub struct ArcSliceW / WeakArcSlice : core::sync::atomic::AtomicUsize);
 
+/// Basically `Rc<[u8]>` but only takes up half the stack space.
+///
+/// This does not support weak handles (see the [module docs](self#weak-handles)), but [`RcBytesW`] does.
+pub type RcBytes = RcSlice<u8>;
+/// Basically `Arc<[u8]>` but only takes up half the stack space.
+///
+/// This does not support weak handles (see the [module docs](self#weak-handles)), but [`ArcBytesW`] does.
+#[cfg(target_has_atomic = "ptr")]
+pub type ArcBytes = ArcSlice<u8>;
+/// Like [`RcBytes`], but with the content aligned to `A` bytes.
+pub type RcBytesAligned<const A: usize> = RcSlice<u8, A>;
+/// Like [`ArcBytes`], but with the content aligned to `A` bytes.
+#[cfg(target_has_atomic = "ptr")]
+pub type ArcBytesAligned<const A: usize> = ArcSlice<u8, A>;
+/// Like [`RcBytes`], but with support for weak handles.
+pub type RcBytesW = RcSliceW<u8>;
+/// A weak handle to an [`RcBytesW`] which does not keep the content alive.
+pub type WeakRcBytes = WeakRcSlice<u8>;
+/// Like [`ArcBytes`], but with support for weak handles.
 #[cfg(target_has_atomic = "ptr")]
-impl Counter for core::sync::atomic::AtomicUsize {
-    fn increment(&self) {
-        self.fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+pub type ArcBytesW = ArcSliceW<u8>;
+/// A weak handle to an [`ArcBytesW`] which does not keep the content alive.
+#[cfg(target_has_atomic = "ptr")]
+pub type WeakArcBytes = WeakArcSlice<u8>;
+
+/// The error returned when trying to construct a compact comrade with more than `u32::MAX` bytes of content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError;
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "content length exceeds u32::MAX")
     }
-    fn decrement(&self) -> usize {
-        self.fetch_sub(1, core::sync::atomic::Ordering::AcqRel) - 1
+}
+
+impl core::error::Error for CapacityError {}
+
+// compact header layout: [count: u32][content length: u32][content...]
+const LEN32: usize = 4;
+const DATA32: usize = 8;
+
+/// Gets the layout for a compact buffer with the given content length, failing if the length does not fit in a `u32`.
+fn layout32(len: usize) -> Result<Layout, CapacityError> {
+    match u32::try_from(len).ok().and_then(|_| len.checked_add(DATA32)).map(|size| Layout::from_size_align(size, align_of::<u32>())) {
+        Some(Ok(layout)) => Ok(layout),
+        _ => Err(CapacityError),
     }
 }
 
-macro_rules! make_comrade {
+macro_rules! make_compact_comrade {
     ($(#[$m:meta])? $vis:vis struct $name:ident : $counter:ty) => {
         $(#[$m])?
         ///
-        /// We do not support weak semantics, as all are equal under socialism.
+        /// The header only holds a `u32` reference count and a `u32` length (8 bytes total), so content is limited to `u32::MAX` bytes and weak handles are not supported.
+        /// Fallible construction is available via [`try_from_slice`](Self::try_from_slice), while [`From`] (and [`Comrade::from_slice`](crate::Comrade::from_slice)) panic on oversized content.
         $vis struct $name(NonNull<u8>);
 
-        impl From<&[u8]> for $name {
-            fn from(value: &[u8]) -> Self {
-                debug_assert!(align_of::<$counter>() == ALIGN && size_of::<$counter>() == ALIGN);
+        impl $name {
+            /// Gets the number of handles to the content.
+            pub fn strong_count(this: &Self) -> usize {
+                unsafe { Counter::load(Self::count(this.0)) }
+            }
+            fn allocation_size(this: &Self) -> usize {
+                unsafe { DATA32 + Self::content_len(this.0) }
+            }
+            unsafe fn count<'a>(ptr: NonNull<u8>) -> &'a $counter {
+                unsafe { &*(ptr.as_ptr() as *const $counter) }
+            }
+            unsafe fn content_len(ptr: NonNull<u8>) -> usize {
+                unsafe { *(ptr.as_ptr().add(LEN32) as *const u32) as usize }
+            }
+            /// Attempts to construct a new instance holding a copy of the given content, failing if it is longer than `u32::MAX` bytes.
+            pub fn try_from_slice(value: &[u8]) -> Result<Self, CapacityError> {
+                debug_assert!(align_of::<$counter>() == align_of::<u32>() && size_of::<$counter>() == LEN32);
 
+                let layout = layout32(value.len())?;
                 unsafe {
-                    let ptr = alloc::alloc::alloc(alloc::alloc::Layout::from_size_align(2 * ALIGN + value.len(), ALIGN).unwrap_unchecked());
-                    *(ptr as *mut $counter) = <$counter>::new(1);
-                    *(ptr.add(ALIGN) as *mut usize) = value.len();
-                    ptr.add(2 * ALIGN).copy_from_nonoverlapping(value.as_ptr(), value.len());
-                    Self(NonNull::new_unchecked(ptr))
+                    let ptr = alloc::alloc::alloc(layout);
+                    if ptr.is_null() {
+                        alloc::alloc::handle_alloc_error(layout);
+                    }
+                    (ptr as *mut $counter).write(<$counter>::new(1));
+                    (ptr.add(LEN32) as *mut u32).write(value.len() as u32);
+                    ptr.add(DATA32).copy_from_nonoverlapping(value.as_ptr(), value.len());
+                    Ok(Self(NonNull::new_unchecked(ptr)))
+                }
+            }
+        }
+
+        impl From<&[u8]> for $name {
+            fn from(value: &[u8]) -> Self {
+                match Self::try_from_slice(value) {
+                    Ok(res) => res,
+                    Err(_) => panic!("capacity overflow"),
                 }
             }
         }
 
         impl Clone for $name {
             fn clone(&self) -> Self {
-                unsafe { (*(self.0.as_ptr() as *const $counter)).increment(); }
+                unsafe { Self::count(self.0).increment(); }
                 Self(self.0)
             }
         }
@@ -67,8 +524,8 @@ macro_rules! make_comrade {
         impl Drop for $name {
             fn drop(&mut self) {
                 unsafe {
-                    if (*(self.0.as_ptr() as *const $counter)).decrement() == 0 {
-                        alloc::alloc::dealloc(self.0.as_ptr(), alloc::alloc::Layout::from_size_align(2 * ALIGN + *(self.0.as_ptr().add(ALIGN) as *const usize), ALIGN).unwrap_unchecked());
+                    if Self::count(self.0).decrement() == 0 {
+                        alloc::alloc::dealloc(self.0.as_ptr(), Layout::from_size_align_unchecked(DATA32 + Self::content_len(self.0), align_of::<u32>()));
                     }
                 }
             }
@@ -77,75 +534,346 @@ macro_rules! make_comrade {
         impl Deref for $name {
             type Target = [u8];
             fn deref(&self) -> &Self::Target {
-                unsafe { core::slice::from_raw_parts(self.0.as_ptr().add(2 * ALIGN), *(self.0.as_ptr().add(ALIGN) as *const usize)) }
+                unsafe { core::slice::from_raw_parts(self.0.as_ptr().add(DATA32), Self::content_len(self.0)) }
             }
         }
 
-        impl Default for $name {
-            fn default() -> Self {
-                Self::from([].as_slice())
-            }
-        }
+        impl_slice_traits!([] $name : u8);
 
-        impl AsRef<[u8]> for $name {
-            fn as_ref(&self) -> &[u8] {
-                self
+        unsafe impl crate::ThinComrade for $name {
+            fn into_raw(self) -> NonNull<u8> {
+                core::mem::ManuallyDrop::new(self).0
+            }
+            unsafe fn from_raw(ptr: NonNull<u8>) -> Self {
+                Self(ptr)
             }
         }
+    };
+}
+make_compact_comrade!(#[doc = "Like [`RcBytes`], but with a compact 8-byte header."] pub struct RcBytes32 : Cell<u32>);
+#[cfg(target_has_atomic = "32")]
+make_compact_comrade!(#[doc = "Like [`ArcBytes`], but with a compact 8-byte header."] pub struct ArcBytes32 : core::sync::atomic::AtomicU32);
 
-        impl Borrow<[u8]> for $name {
-            fn borrow(&self) -> &[u8] {
-                self
+macro_rules! make_str_comrade {
+    ($(#[$m:meta])? $vis:vis struct $name:ident($($p:ident::)*{$t:ident, $w:ident})) => {
+        $(#[$m])?
+        ///
+        /// This can hold arbitrary bytes (so it also works with [`OurBytes`](crate::OurBytes)), but can wrap a shared `str` handle without copying,
+        /// which lets [`OurString`](crate::OurString) do so without re-checking UTF-8 (see [`StrComrade`](crate::StrComrade)).
+        #[derive(Clone)]
+        $vis struct $name($($p::)*$t<[u8]>);
+
+        impl $name {
+            /// Gets the number of handles to the content.
+            pub fn strong_count(this: &Self) -> usize {
+                $($p::)*$t::strong_count(&this.0)
+            }
+            fn allocation_size(this: &Self) -> usize {
+                crate::counted_size(Layout::for_value::<[u8]>(&this.0))
+            }
+            /// Converts this into a shared `str` handle (reusing the allocation), or returns it unchanged if the content is not valid UTF-8.
+            pub fn into_str(self) -> Result<$($p::)*$t<str>, Self> {
+                match core::str::from_utf8(&self.0) {
+                    Ok(_) => Ok(unsafe { $($p::)*$t::from_raw($($p::)*$t::into_raw(self.0) as *const str) }),
+                    Err(_) => Err(self),
+                }
             }
         }
 
-        impl Hash for $name {
-            fn hash<H: Hasher>(&self, state: &mut H) {
-                (**self).hash(state);
+        impl From<&[u8]> for $name {
+            fn from(value: &[u8]) -> Self {
+                Self(value.into())
             }
         }
 
-        impl fmt::Debug for $name {
-            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                write!(f, "{:?}", &**self)
+        impl From<$($p::)*$t<str>> for $name {
+            fn from(value: $($p::)*$t<str>) -> Self {
+                Self(value.into())
             }
         }
 
-        impl<T: AsRef<[u8]>> PartialEq<T> for $name {
-            fn eq(&self, other: &T) -> bool {
-                (**self).eq(other.as_ref())
+        impl Deref for $name {
+            type Target = [u8];
+            fn deref(&self) -> &Self::Target {
+                &self.0
             }
         }
 
-        impl Eq for $name {}
+        impl_slice_traits!([] $name : u8);
 
-        impl<T: AsRef<[u8]>> PartialOrd<T> for $name {
-            fn partial_cmp(&self, other: &T) -> Option<Ordering> {
-                (**self).partial_cmp(other.as_ref())
+        impl<const N: usize> From<$($p::)*$t<str>> for crate::OurString<$name, N> {
+            fn from(value: $($p::)*$t<str>) -> Self {
+                Self::from_str_handle(value)
             }
         }
 
-        impl Ord for $name {
-            fn cmp(&self, other: &Self) -> Ordering {
-                (**self).cmp(&**other)
+        unsafe impl crate::StrComrade for $name {
+            type Str = $($p::)*$t<str>;
+            fn from_str_handle(value: Self::Str) -> Self {
+                value.into()
             }
         }
 
-        impl crate::Comrade for $name {
-            fn from_slice(s: &[u8]) -> Self {
-                s.into()
+        impl crate::WeakComrade for $name {
+            type Weak = $($p::)*$w<[u8]>;
+            fn downgrade(&self) -> Self::Weak {
+                $($p::)*$t::downgrade(&self.0)
             }
-            fn as_slice(&self) -> &[u8] {
-                self
+            fn upgrade(weak: &Self::Weak) -> Option<Self> {
+                weak.upgrade().map(Self)
             }
         }
     };
 }
-make_comrade!(#[doc = "Basically `Rc<[u8]>` but only takes up half the stack space."] pub struct RcBytes : Cell<usize>);
+make_str_comrade!(#[doc = "A shared handle like `Rc<[u8]>`, which can be made from an `Rc<str>` without copying."] pub struct RcStr(alloc::rc::{Rc, Weak}));
+#[cfg(target_has_atomic = "ptr")]
+make_str_comrade!(#[doc = "A shared handle like `Arc<[u8]>`, which can be made from an `Arc<str>` without copying."] pub struct ArcStr(alloc::sync::{Arc, Weak}));
+
+/// Converts between comrades with the same header layout (but different counters),
+/// reusing the allocation in place if the source is the only handle (strong or weak) to its content.
+macro_rules! impl_comrade_from {
+    ($($from:ident => $to:ident : $counter:ty),*) => {$(
+        impl<const A: usize> crate::ComradeFrom<$from<u8, A>> for $to<u8, A> {
+            fn comrade_from(value: $from<u8, A>) -> Self {
+                let ptr = crate::ThinComrade::into_raw(value);
+                unsafe {
+                    if !$from::<u8, A>::strong(ptr).take_unique() {
+                        return $to::<u8, A>::from(&*$from::<u8, A>(ptr, PhantomData));
+                    }
+                    (ptr.as_ptr() as *mut $counter).write(<$counter>::new(1));
+                    $to(ptr, PhantomData)
+                }
+            }
+        }
+    )*};
+    ($(weak $from:ident => $to:ident : $counter:ty),*) => {$(
+        impl<const A: usize> crate::ComradeFrom<$from<u8, A>> for $to<u8, A> {
+            fn comrade_from(value: $from<u8, A>) -> Self {
+                let ptr = crate::ThinComrade::into_raw(value);
+                unsafe {
+                    // once the strong count hits zero, no new handles can be made, so we can safely check the weak count
+                    if !$from::<u8, A>::strong(ptr).take_unique() {
+                        return $to::<u8, A>::from(&*$from::<u8, A>(ptr, PhantomData));
+                    }
+                    if Counter::load($from::<u8, A>::weak(ptr)) != 1 {
+                        let res = $to::<u8, A>::from($from::<u8, A>::content(ptr));
+                        $from::<u8, A>::release_weak(ptr);
+                        return res;
+                    }
+                    (ptr.as_ptr() as *mut $counter).write(<$counter>::new(1));
+                    (ptr.as_ptr().add(WEAK) as *mut $counter).write(<$counter>::new(1));
+                    $to(ptr, PhantomData)
+                }
+            }
+        }
+    )*};
+}
+#[cfg(target_has_atomic = "ptr")]
+impl_comrade_from! { RcSlice => ArcSlice : core::sync::atomic::AtomicUsize, ArcSlice => RcSlice : Cell<usize> }
 #[cfg(target_has_atomic = "ptr")]
-make_comrade!(#[doc = "Basically `Arc<[u8]>` but only takes up half the stack space."] pub struct ArcBytes : core::sync::atomic::AtomicUsize);
+impl_comrade_from! { weak RcSliceW => ArcSliceW : core::sync::atomic::AtomicUsize, weak ArcSliceW => RcSliceW : Cell<usize> }
 
 #[cfg(target_has_atomic = "ptr")]
-unsafe impl Send for ArcBytes {}
+unsafe impl<E: Copy + Send + Sync, const A: usize> Send for ArcSlice<E, A> {}
 #[cfg(target_has_atomic = "ptr")]
-unsafe impl Sync for ArcBytes {}
+unsafe impl<E: Copy + Send + Sync, const A: usize> Sync for ArcSlice<E, A> {}
+#[cfg(target_has_atomic = "ptr")]
+unsafe impl<E: Copy + Send + Sync, const A: usize> Send for ArcSliceW<E, A> {}
+#[cfg(target_has_atomic = "ptr")]
+unsafe impl<E: Copy + Send + Sync, const A: usize> Sync for ArcSliceW<E, A> {}
+#[cfg(target_has_atomic = "ptr")]
+unsafe impl<E: Copy + Send + Sync, const A: usize> Send for WeakArcSlice<E, A> {}
+#[cfg(target_has_atomic = "ptr")]
+unsafe impl<E: Copy + Send + Sync, const A: usize> Sync for WeakArcSlice<E, A> {}
+#[cfg(target_has_atomic = "32")]
+unsafe impl Send for ArcBytes32 {}
+#[cfg(target_has_atomic = "32")]
+unsafe impl Sync for ArcBytes32 {}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicUsize;
+
+    /// Runs `f` in a child test process (re-running this test binary with `name` selected) and checks that it aborted due to refcount overflow.
+    fn assert_aborts(name: &str, f: impl FnOnce()) {
+        if std::env::var("OUR_STRING_ABORT_TEST").as_deref() == Ok(name) {
+            f();
+            return;
+        }
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args([&std::format!("comrades::tests::{name}"), "--exact", "--nocapture", "--test-threads=1"])
+            .env("OUR_STRING_ABORT_TEST", name)
+            .output().unwrap();
+        assert!(!output.status.success(), "child process did not abort");
+        assert!(std::string::String::from_utf8_lossy(&output.stderr).contains("reference count overflow"));
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn test_layout_overflow() {
+        layout::<u8, 1>(HEADER, usize::MAX - 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn test_layout_overflow_elements() {
+        layout::<u64, 1>(HEADER, usize::MAX / 4);
+    }
+
+    #[test]
+    fn test_layout_max() {
+        // the size rounded up to the alignment must not exceed `isize::MAX`
+        let len = isize::MAX as usize + 1 - align::<u8, 1>() - data::<u8, 1>(HEADER);
+        assert_eq!(layout::<u8, 1>(HEADER, len).size(), isize::MAX as usize + 1 - align::<u8, 1>());
+        assert_eq!(std::panic::catch_unwind(|| layout::<u8, 1>(HEADER, len + 1)).is_err(), true);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_layout32_capacity() {
+        assert_eq!(layout32(u32::MAX as usize).unwrap().size(), u32::MAX as usize + DATA32);
+        assert_eq!(layout32(u32::MAX as usize + 1), Err(CapacityError));
+        assert_eq!(layout32(usize::MAX), Err(CapacityError));
+    }
+
+    #[test]
+    fn test_counter_below_max() {
+        let c = Cell::new(MAX_REFCOUNT - 1);
+        c.increment();
+        assert_eq!(c.get(), MAX_REFCOUNT);
+        let c = AtomicUsize::new(MAX_REFCOUNT - 1);
+        c.increment();
+        assert_eq!(Counter::load(&c), MAX_REFCOUNT);
+        let c = Cell::new(MAX_REFCOUNT32 - 1);
+        c.increment();
+        assert_eq!(c.get(), MAX_REFCOUNT32);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "miri cannot spawn processes")]
+    fn test_counter_overflow_cell() {
+        assert_aborts("test_counter_overflow_cell", || Cell::new(MAX_REFCOUNT).increment());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "miri cannot spawn processes")]
+    fn test_counter_overflow_atomic() {
+        assert_aborts("test_counter_overflow_atomic", || AtomicUsize::new(MAX_REFCOUNT).increment());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "miri cannot spawn processes")]
+    fn test_counter_overflow_upgrade() {
+        assert_aborts("test_counter_overflow_upgrade", || { AtomicUsize::new(MAX_REFCOUNT).upgrade(); });
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "miri cannot spawn processes")]
+    fn test_counter_overflow_compact() {
+        assert_aborts("test_counter_overflow_compact", || Cell::new(MAX_REFCOUNT32).increment());
+    }
+
+    /// A small model of the atomic counter protocol (clone/drop racing with upgrade and unique conversion), sized to also run under Miri.
+    #[test]
+    #[cfg(target_has_atomic = "ptr")]
+    fn test_counter_model() {
+        let (threads, iters) = if cfg!(miri) { (3, 10) } else { (8, 1000) };
+        for _ in 0..if cfg!(miri) { 2 } else { 20 } {
+            let value = ArcBytesW::from(b"hello world".as_slice());
+            let weak = ArcBytesW::downgrade(&value);
+            let done = Arc::new(AtomicUsize::new(0));
+            let handles = (0..threads).map(|i| {
+                let (value, weak, done) = (value.clone(), weak.clone(), done.clone());
+                std::thread::spawn(move || {
+                    for _ in 0..iters {
+                        match i % 2 {
+                            0 => drop(value.clone()),
+                            _ => if let Some(x) = weak.upgrade() { assert_eq!(&*x, b"hello world"); },
+                        }
+                    }
+                    drop(value);
+                    done.fetch_add(1, core::sync::atomic::Ordering::SeqCst);
+                })
+            }).collect::<std::vec::Vec<_>>();
+            let converted = <RcBytesW as crate::ComradeFrom<ArcBytesW>>::comrade_from(value);
+            assert_eq!(&*converted, b"hello world");
+            for handle in handles {
+                handle.join().unwrap();
+            }
+            assert_eq!(done.load(core::sync::atomic::Ordering::SeqCst), threads);
+            assert_eq!(weak.upgrade().is_none(), true);
+        }
+    }
+
+    /// Loom models of the atomic counter protocol, run via `RUSTFLAGS="--cfg loom" cargo test --release --lib loom`.
+    /// The content is modeled by a loom cell, so that missing happens-before edges are reported as data races.
+    #[cfg(loom)]
+    mod loom_models {
+        use super::*;
+
+        use loom::cell::UnsafeCell;
+        use loom::sync::atomic::AtomicUsize;
+        use loom::sync::Arc;
+
+        #[test]
+        fn loom_release() {
+            // every handle reads the content before dropping, and only the last one may free (write) it
+            loom::model(|| {
+                let shared = Arc::new((AtomicUsize::new(2), UnsafeCell::new(0u8)));
+                let other = shared.clone();
+                let drop_handle = move |shared: Arc<(AtomicUsize, UnsafeCell<u8>)>| {
+                    shared.1.with(|x| assert_eq!(unsafe { *x }, 0));
+                    if shared.0.decrement() == 0 {
+                        shared.1.with_mut(|x| unsafe { *x = 1 });
+                    }
+                };
+                let thread = loom::thread::spawn(move || drop_handle(other));
+                drop_handle(shared);
+                thread.join().unwrap();
+            });
+        }
+
+        #[test]
+        fn loom_take_unique_vs_upgrade() {
+            // a weak upgrade races with in-place reuse of the (apparently) unique allocation
+            loom::model(|| {
+                let shared = Arc::new((AtomicUsize::new(1), UnsafeCell::new(0u8)));
+                let other = shared.clone();
+                let thread = loom::thread::spawn(move || {
+                    if other.0.upgrade() {
+                        other.1.with(|x| assert_eq!(unsafe { *x }, 0));
+                        other.0.decrement();
+                    }
+                });
+                if shared.0.take_unique() {
+                    shared.1.with_mut(|x| unsafe { *x = 1 });
+                } else {
+                    shared.0.decrement();
+                }
+                thread.join().unwrap();
+            });
+        }
+
+        #[test]
+        fn loom_clone_drop() {
+            // concurrent clones and drops never lose a count, so neither drop observes zero while the original handle is alive
+            loom::model(|| {
+                let count = Arc::new(AtomicUsize::new(1));
+                let other = count.clone();
+                let thread = loom::thread::spawn(move || {
+                    other.increment();
+                    other.decrement()
+                });
+                count.increment();
+                let a = count.decrement();
+                let b = thread.join().unwrap();
+                assert_eq!((a >= 1, b >= 1, Counter::load(&*count)), (true, true, 1));
+                assert_eq!(count.decrement(), 0);
+            });
+        }
+    }
+}
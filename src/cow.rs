@@ -0,0 +1,148 @@
+use core::cmp::{Ordering, PartialEq, Eq, PartialOrd, Ord};
+use core::fmt::{self, Debug, Display};
+use core::borrow::Borrow;
+use core::ops::Deref;
+use core::hash::Hash;
+
+use crate::{Comrade, OurString};
+
+/// Either a borrowed `&str` or an owned [`OurString`].
+///
+/// This is useful for parsers which want to return string fields borrowed from the input buffer,
+/// and only promote them to [`OurString`] (via [`into_owned`](Self::into_owned)) if they need to outlive it.
+///
+/// This type has the same [`Deref`], [`Hash`], [`Eq`], and [`Ord`] semantics as [`OurString`] (i.e., those of [`str`]),
+/// so borrowed and owned values can be mixed freely as map keys.
+pub enum OurCow<'a, T: Comrade, const N: usize> {
+    Borrowed(&'a str),
+    Owned(OurString<T, N>),
+}
+
+impl<'a, T: Comrade, const N: usize> OurCow<'a, T, N> {
+    /// Gets a shared reference to the content.
+    pub fn as_str(&self) -> &str {
+        self
+    }
+    /// Checks if the content is borrowed.
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self, Self::Borrowed(_))
+    }
+    /// Checks if the content is owned.
+    pub fn is_owned(&self) -> bool {
+        matches!(self, Self::Owned(_))
+    }
+    /// Converts this value into an [`OurString`].
+    ///
+    /// Borrowed content is inlined if it fits, and otherwise copied into a new shared `T` allocation.
+    pub fn into_owned(self) -> OurString<T, N> {
+        match self {
+            Self::Borrowed(value) => OurString::from(value),
+            Self::Owned(value) => value,
+        }
+    }
+}
+
+impl<T: Comrade, const N: usize> Deref for OurCow<'_, T, N> {
+    type Target = str;
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Borrowed(value) => value,
+            Self::Owned(value) => value,
+        }
+    }
+}
+
+impl<T: Comrade + Clone, const N: usize> Clone for OurCow<'_, T, N> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Borrowed(value) => Self::Borrowed(value),
+            Self::Owned(value) => Self::Owned(value.clone()),
+        }
+    }
+}
+
+impl<'a, T: Comrade, const N: usize> From<&'a str> for OurCow<'a, T, N> {
+    fn from(value: &'a str) -> Self {
+        Self::Borrowed(value)
+    }
+}
+
+impl<T: Comrade, const N: usize> From<OurString<T, N>> for OurCow<'_, T, N> {
+    fn from(value: OurString<T, N>) -> Self {
+        Self::Owned(value)
+    }
+}
+
+impl<T: Comrade, const N: usize> From<OurCow<'_, T, N>> for OurString<T, N> {
+    fn from(value: OurCow<'_, T, N>) -> Self {
+        value.into_owned()
+    }
+}
+
+impl<T: Comrade, const N: usize> Default for OurCow<'_, T, N> {
+    fn default() -> Self {
+        Self::Borrowed("")
+    }
+}
+
+impl<T: Comrade, const N: usize> AsRef<str> for OurCow<'_, T, N> {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl<T: Comrade, const N: usize> Borrow<str> for OurCow<'_, T, N> {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl<T: Comrade, const N: usize> Debug for OurCow<'_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <str as Debug>::fmt(&**self, f)
+    }
+}
+
+impl<T: Comrade, const N: usize> Display for OurCow<'_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <str as Display>::fmt(&**self, f)
+    }
+}
+
+impl<T: Comrade, const N: usize> Hash for OurCow<'_, T, N> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl<U: Deref<Target = str>, T: Comrade, const N: usize> PartialEq<U> for OurCow<'_, T, N> {
+    fn eq(&self, other: &U) -> bool {
+        (**self).eq(&**other)
+    }
+}
+
+impl<T: Comrade, const N: usize> PartialEq<OurCow<'_, T, N>> for &str {
+    fn eq(&self, other: &OurCow<'_, T, N>) -> bool {
+        (**self).eq(&**other)
+    }
+}
+
+impl<T: Comrade, const N: usize> Eq for OurCow<'_, T, N> {}
+
+impl<U: Deref<Target = str>, T: Comrade, const N: usize> PartialOrd<U> for OurCow<'_, T, N> {
+    fn partial_cmp(&self, other: &U) -> Option<Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<T: Comrade, const N: usize> PartialOrd<OurCow<'_, T, N>> for &str {
+    fn partial_cmp(&self, other: &OurCow<'_, T, N>) -> Option<Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<T: Comrade, const N: usize> Ord for OurCow<'_, T, N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (**self).cmp(&**other)
+    }
+}
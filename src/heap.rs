@@ -0,0 +1,65 @@
+use alloc::collections::BTreeSet;
+
+use crate::{Comrade, OurSlice, OurString};
+
+/// Sums the heap memory used by many [`OurSlice`] (or [`OurBytes`](crate::OurBytes)) and [`OurString`] values, counting each shared allocation only once.
+///
+/// Values can be added individually or via [`Extend`], e.g., `usage.extend(map.keys())`.
+/// Shared content is identified by [`Comrade::identity`], so handles are counted once if and only if they are [`ptr_eq`](Comrade::ptr_eq).
+#[derive(Default, Clone, Debug)]
+pub struct HeapUsage {
+    seen: BTreeSet<(usize, usize)>,
+    total: usize,
+}
+
+impl HeapUsage {
+    /// Creates a new empty [`HeapUsage`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Gets the total number of heap bytes used by all values added so far.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+    /// Gets the number of distinct shared allocations counted so far.
+    pub fn allocations(&self) -> usize {
+        self.seen.len()
+    }
+    /// Adds the heap memory used by the given value (see [`OurSlice::heap_size`]), unless its shared content was already counted.
+    ///
+    /// Returns the number of newly counted bytes.
+    pub fn add_slice<E: Copy, T: Comrade<E>, const N: usize>(&mut self, value: &OurSlice<E, T, N>) -> usize {
+        let content = match value.as_comrade() {
+            Some(content) => content,
+            None => return 0,
+        };
+        if !self.seen.insert(content.identity()) {
+            return 0;
+        }
+        let size = value.heap_size();
+        self.total += size;
+        size
+    }
+    /// Adds the heap memory used by the given value (see [`OurString::heap_size`]), unless its shared content was already counted.
+    ///
+    /// Returns the number of newly counted bytes.
+    pub fn add_string<T: Comrade, const N: usize>(&mut self, value: &OurString<T, N>) -> usize {
+        self.add_slice(value.as_bytes_container())
+    }
+}
+
+impl<'a, E: Copy + 'a, T: Comrade<E> + 'a, const N: usize> Extend<&'a OurSlice<E, T, N>> for HeapUsage {
+    fn extend<I: IntoIterator<Item = &'a OurSlice<E, T, N>>>(&mut self, iter: I) {
+        for value in iter {
+            self.add_slice(value);
+        }
+    }
+}
+
+impl<'a, T: Comrade + 'a, const N: usize> Extend<&'a OurString<T, N>> for HeapUsage {
+    fn extend<I: IntoIterator<Item = &'a OurString<T, N>>>(&mut self, iter: I) {
+        for value in iter {
+            self.add_string(value);
+        }
+    }
+}
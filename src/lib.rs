@@ -2,32 +2,202 @@
 #![doc = include_str!("../README.md")]
 
 extern crate alloc;
+#[cfg(any(test, feature = "testing"))]
+extern crate std;
 
 /// Represents a socialist data container.
 ///
 /// This should only be implemented on types that have semantics similar to [`Rc`](alloc::rc::Rc) or [`Arc`](alloc::sync::Arc).
-pub trait Comrade {
-    fn from_slice(s: &[u8]) -> Self;
-    fn as_slice(&self) -> &[u8];
+///
+/// The element type `E` defaults to `u8`, which is what [`OurBytes`] and [`OurString`] use; other element types can be used with [`OurSlice`].
+pub trait Comrade<E: Copy = u8> {
+    fn from_slice(s: &[E]) -> Self;
+    fn as_slice(&self) -> &[E];
+    /// Checks if two handles share the same content.
+    ///
+    /// This is used as a fast path for comparisons and by [`ById`], so it must only return `true` if both [`as_slice`](Comrade::as_slice) results are the exact same slice (address and length).
+    /// The default implementation compares [`identity`](Comrade::identity) keys, and any override must agree with them.
+    fn ptr_eq(&self, other: &Self) -> bool {
+        self.identity() == other.identity()
+    }
+    /// Gets a key identifying the shared content, which is equal for two handles if and only if they are [`ptr_eq`](Comrade::ptr_eq).
+    ///
+    /// This is used by [`ById`] and [`HeapUsage`] to hash and deduplicate shared content.
+    /// The default implementation uses the address and length of [`as_slice`](Comrade::as_slice),
+    /// but types which may hand out the same (e.g., empty) slice for unrelated handles should identify the handles instead.
+    fn identity(&self) -> (usize, usize) {
+        let content = self.as_slice();
+        (content.as_ptr() as usize, content.len())
+    }
+    /// Gets the number of strong handles to the shared content, if supported.
+    fn strong_count(&self) -> Option<usize> {
+        None
+    }
+    /// Gets the number of heap bytes used by the shared content (including headers and indirection), if supported.
+    ///
+    /// This is the total size of the shared allocation(s), regardless of how many handles share it.
+    fn heap_size(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// A [`Comrade`] which supports weak handles that do not keep the shared content alive.
+pub trait WeakComrade<E: Copy = u8>: Comrade<E> + Sized {
+    type Weak: Clone;
+    fn downgrade(&self) -> Self::Weak;
+    fn upgrade(weak: &Self::Weak) -> Option<Self>;
+}
+
+/// A [`Comrade`] which can be converted to and from a single (thin) pointer.
+///
+/// # Safety
+///
+/// The pointer returned by [`into_raw`](ThinComrade::into_raw) must be aligned to at least 2 bytes,
+/// and passing it back to [`from_raw`](ThinComrade::from_raw) must restore the original handle.
+/// Additionally, [`as_slice`](Comrade::as_slice) must point into memory owned by the shared allocation (never into the handle value itself),
+/// and must remain valid for as long as any handle to that allocation is alive,
+/// since it may be called on a temporary handle rebuilt via [`from_raw`](ThinComrade::from_raw) which does not outlive the call.
+pub unsafe trait ThinComrade: Comrade + Clone {
+    fn into_raw(self) -> core::ptr::NonNull<u8>;
+    /// # Safety
+    ///
+    /// The pointer must have come from [`into_raw`](ThinComrade::into_raw) and must not be used again afterwards.
+    unsafe fn from_raw(ptr: core::ptr::NonNull<u8>) -> Self;
+}
+
+/// A [`Comrade`] which can be made from a shared `str` handle of type [`Str`](StrComrade::Str) without copying.
+///
+/// This allows wrapping such a handle in an [`OurString`] via [`From`] without re-checking UTF-8.
+/// It is implemented by [`RcStr`](comrades::RcStr) (for `Rc<str>`) and [`ArcStr`](comrades::ArcStr) (for `Arc<str>`),
+/// which hold arbitrary bytes otherwise, so they can also be used with [`OurBytes`].
+///
+/// # Safety
+///
+/// The result of [`from_str_handle`](StrComrade::from_str_handle) must hold exactly the (UTF-8) content of the given handle.
+pub unsafe trait StrComrade: Comrade {
+    type Str;
+    fn from_str_handle(value: Self::Str) -> Self;
+}
+
+/// A [`Comrade`] which can be constructed from another [`Comrade`] type `T`.
+///
+/// Implementations should reuse the allocation of `T` where possible, and copy the content otherwise.
+pub trait ComradeFrom<T: Comrade>: Comrade {
+    fn comrade_from(value: T) -> Self;
+}
+
+impl<T: Comrade> ComradeFrom<T> for T {
+    fn comrade_from(value: T) -> Self {
+        value
+    }
+}
+
+macro_rules! impl_comrade_from_copy {
+    ($($(#[$a:meta])* [$($g:tt)*] $from:ty => $to:ty),*$(,)?) => {$(
+        $(#[$a])* impl<$($g)*> ComradeFrom<$from> for $to where $from: Comrade, $to: Comrade {
+            fn comrade_from(value: $from) -> Self { <$to>::from_slice(value.as_slice()) }
+        }
+    )*};
+}
+impl_comrade_from_copy! {
+    #[cfg(target_has_atomic = "ptr")] [T: ?Sized, U: ?Sized] alloc::rc::Rc<T> => alloc::sync::Arc<U>,
+    #[cfg(target_has_atomic = "ptr")] [T: ?Sized, U: ?Sized] alloc::sync::Arc<T> => alloc::rc::Rc<U>,
+    [T: ?Sized] alloc::rc::Rc<T> => comrades::RcBytes,
+    [T: ?Sized] comrades::RcBytes => alloc::rc::Rc<T>,
+    #[cfg(target_has_atomic = "ptr")] [T: ?Sized] alloc::rc::Rc<T> => comrades::ArcBytes,
+    #[cfg(target_has_atomic = "ptr")] [T: ?Sized] comrades::ArcBytes => alloc::rc::Rc<T>,
+    #[cfg(target_has_atomic = "ptr")] [T: ?Sized] alloc::sync::Arc<T> => comrades::RcBytes,
+    #[cfg(target_has_atomic = "ptr")] [T: ?Sized] comrades::RcBytes => alloc::sync::Arc<T>,
+    #[cfg(target_has_atomic = "ptr")] [T: ?Sized] alloc::sync::Arc<T> => comrades::ArcBytes,
+    #[cfg(target_has_atomic = "ptr")] [T: ?Sized] comrades::ArcBytes => alloc::sync::Arc<T>,
+    #[cfg(target_has_atomic = "ptr")] [] comrades::RcStr => comrades::ArcStr,
+    #[cfg(target_has_atomic = "ptr")] [] comrades::ArcStr => comrades::RcStr,
+    [T: ?Sized] alloc::rc::Rc<T> => comrades::RcStr,
+    [T: ?Sized] comrades::RcStr => alloc::rc::Rc<T>,
+    #[cfg(target_has_atomic = "ptr")] [T: ?Sized] alloc::sync::Arc<T> => comrades::RcStr,
+    #[cfg(target_has_atomic = "ptr")] [T: ?Sized] comrades::RcStr => alloc::sync::Arc<T>,
+    #[cfg(target_has_atomic = "ptr")] [T: ?Sized] alloc::rc::Rc<T> => comrades::ArcStr,
+    #[cfg(target_has_atomic = "ptr")] [T: ?Sized] comrades::ArcStr => alloc::rc::Rc<T>,
+    #[cfg(target_has_atomic = "ptr")] [T: ?Sized] alloc::sync::Arc<T> => comrades::ArcStr,
+    #[cfg(target_has_atomic = "ptr")] [T: ?Sized] comrades::ArcStr => alloc::sync::Arc<T>,
+    [] comrades::RcBytes => comrades::RcStr,
+    [] comrades::RcStr => comrades::RcBytes,
+    #[cfg(target_has_atomic = "ptr")] [] comrades::ArcBytes => comrades::RcStr,
+    #[cfg(target_has_atomic = "ptr")] [] comrades::RcStr => comrades::ArcBytes,
+    #[cfg(target_has_atomic = "ptr")] [] comrades::RcBytes => comrades::ArcStr,
+    #[cfg(target_has_atomic = "ptr")] [] comrades::ArcStr => comrades::RcBytes,
+    #[cfg(target_has_atomic = "ptr")] [] comrades::ArcBytes => comrades::ArcStr,
+    #[cfg(target_has_atomic = "ptr")] [] comrades::ArcStr => comrades::ArcBytes,
+}
+
+/// Gets the size of an [`Rc`](alloc::rc::Rc) or [`Arc`](alloc::sync::Arc) allocation (with strong and weak counts) holding a value with the given layout.
+fn counted_size(value: core::alloc::Layout) -> usize {
+    core::alloc::Layout::new::<[usize; 2]>().extend(value).map_or(usize::MAX, |(layout, _)| layout.pad_to_align().size())
 }
 
 macro_rules! impl_comrade {
-    ($($(#[$a:meta])* $t:ident$(::$tt:ident)*),*) => {$(
-        $(#[$a])* impl<T: core::ops::Deref<Target = [u8]> + for<'a> From<&'a [u8]>> Comrade for $t$(::$tt)*<T> {
-            fn from_slice(s: &[u8]) -> Self { $t$(::$tt)*::new(T::from(s)) }
-            fn as_slice(&self) -> &[u8] { self }
+    ($($(#[$a:meta])* $($p:ident::)*{$t:ident, $w:ident}),*) => {$(
+        $(#[$a])* impl<E: Copy, T: core::ops::Deref<Target = [E]> + for<'a> From<&'a [E]>> Comrade<E> for $($p::)*$t<T> {
+            fn from_slice(s: &[E]) -> Self { $($p::)*$t::new(T::from(s)) }
+            fn as_slice(&self) -> &[E] { self }
+            fn identity(&self) -> (usize, usize) { ($($p::)*$t::as_ptr(self) as *const () as usize, 0) }
+            fn strong_count(&self) -> Option<usize> { Some($($p::)*$t::strong_count(self)) }
+            /// The size of the inner container's own buffer is estimated from its length (e.g., ignoring excess [`Vec`](alloc::vec::Vec) capacity).
+            fn heap_size(&self) -> Option<usize> { Some(counted_size(core::alloc::Layout::new::<T>()) + size_of_val(self.as_slice())) }
+        }
+        $(#[$a])* impl<E: Copy> Comrade<E> for $($p::)*$t<[E]> {
+            fn from_slice(s: &[E]) -> Self { $($p::)*$t::from(s) }
+            fn as_slice(&self) -> &[E] { self }
+            fn strong_count(&self) -> Option<usize> { Some($($p::)*$t::strong_count(self)) }
+            fn heap_size(&self) -> Option<usize> { Some(counted_size(core::alloc::Layout::for_value::<[E]>(self))) }
         }
-        $(#[$a])* impl Comrade for $t$(::$tt)*<[u8]> {
-            fn from_slice(s: &[u8]) -> Self { $t$(::$tt)*::from(s) }
-            fn as_slice(&self) -> &[u8] { self }
+        $(#[$a])* impl<E: Copy, T: ?Sized> WeakComrade<E> for $($p::)*$t<T> where Self: Comrade<E> {
+            type Weak = $($p::)*$w<T>;
+            fn downgrade(&self) -> Self::Weak { $($p::)*$t::downgrade(self) }
+            fn upgrade(weak: &Self::Weak) -> Option<Self> { weak.upgrade() }
         }
     )*};
 }
-impl_comrade! { alloc::rc::Rc, #[cfg(target_has_atomic = "ptr")] alloc::sync::Arc }
+impl_comrade! { alloc::rc::{Rc, Weak}, #[cfg(target_has_atomic = "ptr")] alloc::sync::{Arc, Weak} }
 
 mod bytes;
 mod string;
+mod packed;
+mod thin;
+mod umbra;
+mod cow;
+mod by_id;
+mod heap;
+mod num;
 pub mod comrades;
+pub mod share;
+#[cfg(target_has_atomic = "ptr")]
+mod atomic;
+
+#[cfg(feature = "serde")]
+mod serde_impls;
+#[cfg(feature = "rkyv")]
+mod rkyv_impls;
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impls;
+#[cfg(feature = "proptest")]
+mod proptest_impls;
+#[cfg(feature = "bytes")]
+mod bytes_impls;
+#[cfg(feature = "testing")]
+pub mod testing;
 
 pub use bytes::*;
 pub use string::*;
+pub use packed::*;
+pub use thin::*;
+pub use umbra::*;
+pub use cow::*;
+pub use by_id::*;
+pub use heap::*;
+#[cfg(target_has_atomic = "ptr")]
+pub use atomic::*;
+#[cfg(feature = "rkyv")]
+pub use rkyv_impls::*;
+#[cfg(feature = "bytes")]
+pub use bytes_impls::*;
@@ -0,0 +1,119 @@
+use core::fmt::Write;
+use core::mem::MaybeUninit;
+
+use crate::{Comrade, OurBytes, OurString};
+
+/// A writer which formats text into (possibly uninitialized) storage, failing if it runs out of space.
+struct SliceWriter<'a> {
+    content: &'a mut [MaybeUninit<u8>],
+    len: usize,
+}
+
+impl<'a> SliceWriter<'a> {
+    fn new(content: &'a mut [MaybeUninit<u8>]) -> Self {
+        Self { content, len: 0 }
+    }
+    fn as_str(&self) -> &str {
+        unsafe { core::str::from_utf8_unchecked(core::slice::from_raw_parts(self.content.as_ptr() as *const u8, self.len)) }
+    }
+}
+
+impl Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let dst = self.content.get_mut(self.len..self.len + s.len()).ok_or(core::fmt::Error)?;
+        for (dst, src) in dst.iter_mut().zip(s.as_bytes()) {
+            dst.write(*src);
+        }
+        self.len += s.len();
+        Ok(())
+    }
+}
+
+/// Enough space for any `f64` formatted via [`Display`](core::fmt::Display) (the longest being negative subnormals at 327 bytes).
+const F64_LEN: usize = 327;
+/// Enough space for any integer up to 128 bits (39 digits plus a sign).
+const INT_LEN: usize = 40;
+
+macro_rules! write_digits {
+    ($($name:ident : $w:ty),*) => {$(
+        /// Writes `value` in decimal (with a leading `-` if `negative`) to the start of `out`, returning the length,
+        /// or [`None`] if it does not fit.
+        fn $name(mut value: $w, negative: bool, out: &mut [MaybeUninit<u8>]) -> Option<usize> {
+            let len = value.checked_ilog10().map_or(1, |x| x as usize + 1) + negative as usize;
+            let out = out.get_mut(..len)?;
+            let mut pos = len;
+            loop {
+                pos -= 1;
+                out[pos].write(b'0' + (value % 10) as u8);
+                value /= 10;
+                if value == 0 {
+                    break;
+                }
+            }
+            if negative {
+                out[0].write(b'-');
+            }
+            Some(len)
+        }
+    )*};
+}
+write_digits! { write_u64: u64, write_u128: u128 } // smaller integers are widened to avoid slow 128-bit arithmetic
+
+macro_rules! impl_from_int {
+    ($($t:ty => $write:ident),*; $($s:ty => $swrite:ident),*) => {
+        $(impl<T: Comrade, const N: usize> From<$t> for OurString<T, N> {
+            /// Formats the integer in decimal, which is inlined if it fits in `N` bytes.
+            fn from(value: $t) -> Self {
+                Self::from_digits(|out| $write(value.into(), false, out))
+            }
+        })*
+        $(impl<T: Comrade, const N: usize> From<$s> for OurString<T, N> {
+            /// Formats the integer in decimal, which is inlined if it fits in `N` bytes.
+            fn from(value: $s) -> Self {
+                Self::from_digits(|out| $swrite(value.unsigned_abs().into(), value < 0, out))
+            }
+        })*
+    };
+}
+impl_from_int! {
+    u8 => write_u64, u16 => write_u64, u32 => write_u64, u64 => write_u64, u128 => write_u128;
+    i8 => write_u64, i16 => write_u64, i32 => write_u64, i64 => write_u64, i128 => write_u128
+}
+
+impl<T: Comrade, const N: usize> OurString<T, N> {
+    /// Writes ASCII digits directly into the inline storage if they fit, and otherwise via a stack buffer into a new shared `T` allocation.
+    fn from_digits(write: impl Fn(&mut [MaybeUninit<u8>]) -> Option<usize>) -> Self {
+        // SAFETY: the digit writers initialize exactly the (ASCII) prefix whose length they return
+        if let Some(content) = unsafe { OurBytes::try_inline_with(&write) } {
+            return unsafe { Self::from_utf8_unchecked(content) };
+        }
+        let mut buf = [const { MaybeUninit::uninit() }; INT_LEN];
+        let len = write(&mut buf).unwrap();
+        Self::from(SliceWriter { content: &mut buf, len }.as_str())
+    }
+    /// Formats the float in the same way as [`Display`](core::fmt::Display), which is inlined if it fits in `N` bytes.
+    ///
+    /// Formatting happens directly into the inline storage (or a stack buffer if it does not fit), so this never allocates unless the result does not fit inline.
+    pub fn from_f64(value: f64) -> Self {
+        // SAFETY: the writer initializes exactly the prefix whose length is returned, which is valid UTF-8 since it only receives `&str`
+        let inline = unsafe {
+            OurBytes::try_inline_with(|content| {
+                let mut out = SliceWriter::new(content);
+                write!(out, "{value}").ok().map(|()| out.len)
+            })
+        };
+        if let Some(content) = inline {
+            return unsafe { Self::from_utf8_unchecked(content) };
+        }
+        let mut buf = [const { MaybeUninit::uninit() }; F64_LEN];
+        let mut out = SliceWriter::new(&mut buf);
+        match write!(out, "{value}") {
+            Ok(()) => Self::from(out.as_str()),
+            Err(_) => Self::from(alloc::format!("{value}").as_str()),
+        }
+    }
+    /// Gets `"true"` or `"false"`, which is inlined if it fits in `N` bytes.
+    pub fn from_bool(value: bool) -> Self {
+        Self::from(if value { "true" } else { "false" })
+    }
+}
@@ -0,0 +1,205 @@
+use core::cmp::{Ordering, PartialEq, Eq, PartialOrd, Ord};
+use core::fmt::{self, Debug, Display};
+use core::num::NonZero;
+use core::hash::Hash;
+
+use crate::{Comrade, OurString};
+
+const PACKED: u8 = 128; // tag values at or above this denote packed content
+const MAX_PACKED: usize = 126;
+
+#[derive(Clone)]
+enum PackedInner<T, const N: usize> {
+    Inline { tag: NonZero<u8>, content: [u8; N] },
+    Outline { content: T },
+}
+
+/// A customizable immutable shared string which packs ASCII content into 7 bits per character when inlined.
+///
+/// Data is backed inline up to `N` bytes (max 127), or up to `N * 8 / 7` ASCII characters (max 126), or stored dynamically by (shared) [`Comrade`] `T`.
+/// For example, a `PackedOurString<RcBytes, 15>` is the same size as an `OurString<RcBytes, 15>`, but can hold up to 17 ASCII characters without allocating.
+///
+/// Because packed content cannot be borrowed as a `&str`, this type does not implement [`Deref`](core::ops::Deref).
+/// Instead, content can be accessed via [`PackedOurString::with_str`] (which decodes on demand into a stack buffer)
+/// or via [`PackedOurString::as_str`] (which only succeeds for unpacked content).
+///
+/// This type can be constructed via the [`From`] trait given a `&str`, in which case inlining is attempted but may result in a shared `T` allocation.
+#[derive(Clone)]
+pub struct PackedOurString<T: Comrade, const N: usize>(PackedInner<T, N>);
+
+impl<T: Comrade, const N: usize> PackedOurString<T, N> {
+    /// Creates a new empty instance of [`PackedOurString`] with inlined data.
+    pub const fn new() -> Self {
+        Self(PackedInner::Inline { tag: NonZero::<u8>::MAX, content: [0; N] })
+    }
+    /// Gets the length of the content in bytes.
+    pub fn len(&self) -> usize {
+        match &self.0 {
+            PackedInner::Inline { tag, .. } => match !tag.get() {
+                x if x >= PACKED => (x - PACKED) as usize,
+                x => x as usize,
+            },
+            PackedInner::Outline { content } => content.as_slice().len(),
+        }
+    }
+    /// Checks if the content is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Checks if the content is stored in packed form, and thus cannot be borrowed via [`PackedOurString::as_str`].
+    pub fn is_packed(&self) -> bool {
+        matches!(&self.0, PackedInner::Inline { tag, .. } if !tag.get() >= PACKED)
+    }
+    /// Gets a shared reference to the content, or [`None`] if the content is packed.
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.0 {
+            PackedInner::Inline { tag, .. } if !tag.get() >= PACKED => None,
+            PackedInner::Inline { tag, content } => Some(unsafe { core::str::from_utf8_unchecked(&content[..(!tag.get()) as usize]) }),
+            PackedInner::Outline { content } => Some(unsafe { core::str::from_utf8_unchecked(content.as_slice()) }),
+        }
+    }
+    /// Calls the given function with the (decoded) content.
+    ///
+    /// Packed content is decoded into a stack buffer, so this never allocates.
+    pub fn with_str<R>(&self, f: impl FnOnce(&str) -> R) -> R {
+        match (self.as_str(), &self.0) {
+            (Some(s), _) => f(s),
+            (None, PackedInner::Inline { content, .. }) => {
+                let mut buf = [0; MAX_PACKED];
+                let len = self.len();
+                for (i, x) in buf[..len].iter_mut().enumerate() {
+                    *x = unpack(content, i);
+                }
+                f(unsafe { core::str::from_utf8_unchecked(&buf[..len]) })
+            }
+            (None, PackedInner::Outline { .. }) => unreachable!(),
+        }
+    }
+    /// Converts this [`PackedOurString`] instance into an [`OurString`] which uses the same shared type `T`.
+    ///
+    /// If the content of this instance is already allocated via shared handle `T`, that handle will simply be reused without inlining.
+    /// Otherwise, inlining will be attempted, but may fail and result in a new shared `T` allocation.
+    pub fn into_our_string<const M: usize>(self) -> OurString<T, M> {
+        match self.0 {
+            PackedInner::Inline { .. } => self.with_str(|s| OurString::from(s)),
+            PackedInner::Outline { content } => {
+                // SAFETY: outlined content is only ever created from a `&str` (see `From<&str>`)
+                unsafe { OurString::from_utf8_unchecked(content.into()) }
+            }
+        }
+    }
+}
+
+fn pack(content: &mut [u8], i: usize, value: u8) {
+    let (byte, shift) = (i * 7 / 8, i * 7 % 8);
+    let v = (value as u16) << shift;
+    content[byte] |= v as u8;
+    if v >> 8 != 0 {
+        content[byte + 1] |= (v >> 8) as u8;
+    }
+}
+
+fn unpack(content: &[u8], i: usize) -> u8 {
+    let (byte, shift) = (i * 7 / 8, i * 7 % 8);
+    let v = content[byte] as u16 | (content.get(byte + 1).copied().unwrap_or(0) as u16) << 8;
+    ((v >> shift) & 0x7f) as u8
+}
+
+impl<T: Comrade, const N: usize> From<&str> for PackedOurString<T, N> {
+    fn from(value: &str) -> Self {
+        let len = value.len();
+        if len <= N && len < PACKED as usize {
+            let mut content = [0; N];
+            content[..len].copy_from_slice(value.as_bytes());
+            Self(PackedInner::Inline { tag: NonZero::new(!(len as u8)).unwrap(), content })
+        } else if len <= N * 8 / 7 && len <= MAX_PACKED && value.is_ascii() {
+            let mut content = [0; N];
+            for (i, x) in value.bytes().enumerate() {
+                pack(&mut content, i, x);
+            }
+            Self(PackedInner::Inline { tag: NonZero::new(!(len as u8 + PACKED)).unwrap(), content })
+        } else {
+            Self(PackedInner::Outline { content: T::from_slice(value.as_bytes()) })
+        }
+    }
+}
+
+impl<T: Comrade, const N: usize> Default for PackedOurString<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Comrade, const N: usize> Debug for PackedOurString<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.with_str(|s| <str as Debug>::fmt(s, f))
+    }
+}
+
+impl<T: Comrade, const N: usize> Display for PackedOurString<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.with_str(|s| <str as Display>::fmt(s, f))
+    }
+}
+
+impl<T: Comrade, const N: usize> Hash for PackedOurString<T, N> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.with_str(|s| s.hash(state))
+    }
+}
+
+impl<T: Comrade, const N: usize> PartialEq for PackedOurString<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.with_str(|a| other.with_str(|b| a == b))
+    }
+}
+
+impl<T: Comrade, const N: usize> PartialEq<str> for PackedOurString<T, N> {
+    fn eq(&self, other: &str) -> bool {
+        self.len() == other.len() && self.with_str(|a| a == other)
+    }
+}
+
+impl<T: Comrade, const N: usize> PartialEq<&str> for PackedOurString<T, N> {
+    fn eq(&self, other: &&str) -> bool {
+        self.eq(*other)
+    }
+}
+
+impl<T: Comrade, const N: usize> PartialEq<PackedOurString<T, N>> for &str {
+    fn eq(&self, other: &PackedOurString<T, N>) -> bool {
+        other.eq(*self)
+    }
+}
+
+impl<T: Comrade, const N: usize> Eq for PackedOurString<T, N> {}
+
+impl<T: Comrade, const N: usize> PartialOrd for PackedOurString<T, N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Comrade, const N: usize> PartialOrd<str> for PackedOurString<T, N> {
+    fn partial_cmp(&self, other: &str) -> Option<Ordering> {
+        self.with_str(|a| a.partial_cmp(other))
+    }
+}
+
+impl<T: Comrade, const N: usize> PartialOrd<&str> for PackedOurString<T, N> {
+    fn partial_cmp(&self, other: &&str) -> Option<Ordering> {
+        self.partial_cmp(*other)
+    }
+}
+
+impl<T: Comrade, const N: usize> PartialOrd<PackedOurString<T, N>> for &str {
+    fn partial_cmp(&self, other: &PackedOurString<T, N>) -> Option<Ordering> {
+        other.with_str(|b| (*self).partial_cmp(b))
+    }
+}
+
+impl<T: Comrade, const N: usize> Ord for PackedOurString<T, N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.with_str(|a| other.with_str(|b| a.cmp(b)))
+    }
+}
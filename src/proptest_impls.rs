@@ -0,0 +1,68 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use proptest::arbitrary::{Arbitrary, any};
+use proptest::collection::vec;
+use proptest::strategy::{BoxedStrategy, Strategy};
+use proptest::prop_oneof;
+
+use crate::{Comrade, OurBytes, OurString};
+
+const MAX_LEN: usize = 64;
+
+/// Generates content biased towards the inline/outline boundary (`N`, capped at the 254 byte inline limit).
+fn content<const N: usize>() -> impl Strategy<Value = Vec<u8>> {
+    let limit = N.min(u8::MAX as usize - 1);
+    prop_oneof![
+        vec(any::<u8>(), 0..=MAX_LEN),
+        vec(any::<u8>(), limit.saturating_sub(1)..=limit + 1),
+    ]
+}
+
+/// Generates string content biased towards the inline/outline boundary (`N`, capped at the 254 byte inline limit).
+fn str_content<const N: usize>() -> impl Strategy<Value = String> {
+    let limit = N.min(u8::MAX as usize - 1);
+    prop_oneof![
+        any::<String>(),
+        vec(0u8..0x80, limit.saturating_sub(1)..=limit + 1).prop_map(|x| String::from_utf8(x).unwrap()),
+    ]
+}
+
+impl<T: Comrade + 'static, const N: usize> Arbitrary for OurBytes<T, N> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            content::<N>().prop_map(|x| OurBytes::from(x.as_slice())),
+            content::<N>().prop_map(|x| OurBytes::from(T::from_slice(&x))),
+        ].boxed()
+    }
+}
+
+impl<T: Comrade + 'static, const N: usize> Arbitrary for OurString<T, N> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            str_content::<N>().prop_map(|x| OurString::from(x.as_str())),
+            str_content::<N>().prop_map(|x| OurString::from_utf8(OurBytes::from(T::from_slice(x.as_bytes()))).unwrap()),
+        ].boxed()
+    }
+}
+
+impl Arbitrary for crate::comrades::RcBytes {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        vec(any::<u8>(), 0..=MAX_LEN).prop_map(|x| Self::from(x.as_slice())).boxed()
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl Arbitrary for crate::comrades::ArcBytes {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        vec(any::<u8>(), 0..=MAX_LEN).prop_map(|x| Self::from(x.as_slice())).boxed()
+    }
+}
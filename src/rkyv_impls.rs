@@ -0,0 +1,153 @@
+use core::cmp::Ordering;
+use core::borrow::Borrow;
+use core::ops::Deref;
+use core::hash::Hash;
+use core::fmt;
+
+use rkyv::rancor::{Fallible, Source};
+use rkyv::ser::{Allocator, Writer};
+use rkyv::string::{ArchivedString, StringResolver};
+use rkyv::vec::{ArchivedVec, VecResolver};
+use rkyv::{Archive, Deserialize, Place, Portable, Serialize};
+use rkyv::munge::munge;
+
+use crate::{Comrade, OurBytes, OurString};
+
+const INLINE: usize = 7;
+
+/// An archived [`OurBytes`].
+///
+/// Short content (up to 7 bytes) is stored inline in the structure, and longer content is stored out-of-line behind a relative pointer.
+/// Either way, access is zero-copy.
+#[derive(Portable, rkyv::bytecheck::CheckBytes)]
+#[bytecheck(crate = rkyv::bytecheck)]
+#[repr(C)]
+pub struct ArchivedOurBytes {
+    outline: ArchivedVec<u8>, // empty if inlined
+    inline_len: u8,
+    inline: [u8; INLINE],
+}
+
+/// The resolver for an archived [`OurBytes`].
+pub struct OurBytesResolver(VecResolver);
+
+impl ArchivedOurBytes {
+    /// Gets a shared reference to the content.
+    pub fn as_slice(&self) -> &[u8] {
+        match self.outline.is_empty() {
+            true => &self.inline[..(self.inline_len as usize).min(INLINE)],
+            false => self.outline.as_slice(),
+        }
+    }
+    /// Resolves an archived bytes collection from a given slice.
+    pub fn resolve_from_slice(value: &[u8], resolver: OurBytesResolver, out: Place<Self>) {
+        munge!(let ArchivedOurBytes { outline, inline_len, inline } = out);
+        if value.len() <= INLINE {
+            let mut content = [0; INLINE];
+            content[..value.len()].copy_from_slice(value);
+            ArchivedVec::<u8>::resolve_from_len(0, resolver.0, outline);
+            inline_len.write(value.len() as u8);
+            inline.write(content);
+        } else {
+            ArchivedVec::<u8>::resolve_from_len(value.len(), resolver.0, outline);
+            inline_len.write(0);
+            inline.write([0; INLINE]);
+        }
+    }
+    /// Serializes an archived bytes collection from a given slice.
+    pub fn serialize_from_slice<S: Fallible + Allocator + Writer + ?Sized>(value: &[u8], serializer: &mut S) -> Result<OurBytesResolver, S::Error> where S::Error: Source {
+        let value = if value.len() <= INLINE { &[] } else { value };
+        ArchivedVec::<u8>::serialize_from_slice(value, serializer).map(OurBytesResolver)
+    }
+}
+
+impl Deref for ArchivedOurBytes {
+    type Target = [u8];
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl AsRef<[u8]> for ArchivedOurBytes {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl Borrow<[u8]> for ArchivedOurBytes {
+    fn borrow(&self) -> &[u8] {
+        self
+    }
+}
+
+impl fmt::Debug for ArchivedOurBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <[u8] as fmt::Debug>::fmt(&**self, f)
+    }
+}
+
+impl Hash for ArchivedOurBytes {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl<U: Deref<Target = [u8]>> PartialEq<U> for ArchivedOurBytes {
+    fn eq(&self, other: &U) -> bool {
+        (**self).eq(&**other)
+    }
+}
+
+impl Eq for ArchivedOurBytes {}
+
+impl<U: Deref<Target = [u8]>> PartialOrd<U> for ArchivedOurBytes {
+    fn partial_cmp(&self, other: &U) -> Option<Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl Ord for ArchivedOurBytes {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl<T: Comrade, const N: usize> Archive for OurBytes<T, N> {
+    type Archived = ArchivedOurBytes;
+    type Resolver = OurBytesResolver;
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedOurBytes::resolve_from_slice(self, resolver, out);
+    }
+}
+
+impl<S: Fallible + Allocator + Writer + ?Sized, T: Comrade, const N: usize> Serialize<S> for OurBytes<T, N> where S::Error: Source {
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedOurBytes::serialize_from_slice(self, serializer)
+    }
+}
+
+impl<D: Fallible + ?Sized, T: Comrade, const N: usize> Deserialize<OurBytes<T, N>, D> for ArchivedOurBytes {
+    fn deserialize(&self, _: &mut D) -> Result<OurBytes<T, N>, D::Error> {
+        Ok(OurBytes::from(self.as_slice()))
+    }
+}
+
+impl<T: Comrade, const N: usize> Archive for OurString<T, N> {
+    type Archived = ArchivedString;
+    type Resolver = StringResolver;
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedString::resolve_from_str(self, resolver, out);
+    }
+}
+
+impl<S: Fallible + Allocator + Writer + ?Sized, T: Comrade, const N: usize> Serialize<S> for OurString<T, N> where S::Error: Source {
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedString::serialize_from_str(self, serializer)
+    }
+}
+
+impl<D: Fallible + ?Sized, T: Comrade, const N: usize> Deserialize<OurString<T, N>, D> for ArchivedString {
+    fn deserialize(&self, _: &mut D) -> Result<OurString<T, N>, D::Error> {
+        Ok(OurString::from(self.as_str()))
+    }
+}
@@ -0,0 +1,85 @@
+use core::marker::PhantomData;
+use core::fmt;
+
+use alloc::vec::Vec;
+
+use serde::de::{self, Deserialize, Deserializer, Visitor, SeqAccess};
+use serde::ser::{Serialize, Serializer};
+
+use crate::{Comrade, ThinComrade, OurBytes, OurString, ThinOurBytes, ThinOurString, UmbraOurBytes, UmbraOurString, PackedOurString};
+
+struct BytesVisitor<T>(PhantomData<T>);
+
+impl<'de, T: for<'a> From<&'a [u8]>> Visitor<'de> for BytesVisitor<T> {
+    type Value = T;
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "bytes")
+    }
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(T::from(v))
+    }
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(T::from(v.as_bytes()))
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut res = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(x) = seq.next_element()? {
+            res.push(x);
+        }
+        Ok(T::from(&res))
+    }
+}
+
+struct StrVisitor<T>(PhantomData<T>);
+
+impl<'de, T: for<'a> From<&'a str>> Visitor<'de> for StrVisitor<T> {
+    type Value = T;
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a string")
+    }
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(T::from(v))
+    }
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        core::str::from_utf8(v).map(T::from).map_err(|_| E::invalid_value(de::Unexpected::Bytes(v), &self))
+    }
+}
+
+macro_rules! impl_serde {
+    ($visitor:ident : $deserialize:ident => $serialize:ident => $([$($g:tt)*] $t:ty),*$(,)?) => {$(
+        impl<$($g)*> Serialize for $t {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.$serialize(self)
+            }
+        }
+        impl<'de, $($g)*> Deserialize<'de> for $t {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.$deserialize($visitor(PhantomData))
+            }
+        }
+    )*};
+}
+impl_serde! { BytesVisitor : deserialize_bytes => serialize_bytes =>
+    [T: Comrade, const N: usize] OurBytes<T, N>,
+    [T: ThinComrade] ThinOurBytes<T>,
+    [T: Comrade] UmbraOurBytes<T>,
+    [] crate::comrades::RcBytes,
+}
+#[cfg(target_has_atomic = "ptr")]
+impl_serde! { BytesVisitor : deserialize_bytes => serialize_bytes => [] crate::comrades::ArcBytes }
+impl_serde! { StrVisitor : deserialize_str => serialize_str =>
+    [T: Comrade, const N: usize] OurString<T, N>,
+    [T: ThinComrade] ThinOurString<T>,
+    [T: Comrade] UmbraOurString<T>,
+}
+
+impl<T: Comrade, const N: usize> Serialize for PackedOurString<T, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.with_str(|s| serializer.serialize_str(s))
+    }
+}
+impl<'de, T: Comrade, const N: usize> Deserialize<'de> for PackedOurString<T, N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(StrVisitor(PhantomData))
+    }
+}
@@ -0,0 +1,201 @@
+//! Sharing-preserving encoding of [`OurBytes`] and [`OurString`] values.
+//!
+//! Cloning a shared (non-inlined) value is `O(1)`, but naively serializing many clones writes (and later reallocates) the content once per clone.
+//! Instead, [`ShareEncoder`] detects shared handles by identity (see [`Comrade::identity`]) and writes each unique buffer only once,
+//! and [`ShareDecoder`] reconstructs the shared handles on load.
+//!
+//! Values must be read back in the same order they were written, and with the same shared type `T` (though the inlining size `N` may differ).
+//!
+//! ```
+//! # use our_string::share::{ShareEncoder, ShareDecoder};
+//! # use our_string::comrades::RcBytes;
+//! # use our_string::OurString;
+//! let a = OurString::<RcBytes, 8>::from("hello world, how are you?");
+//! let mut encoder = ShareEncoder::new();
+//! for _ in 0..1000 {
+//!     encoder.write_str(&a);
+//! }
+//! let encoded = encoder.finish();
+//! assert!(encoded.len() < 2 * 1000 + a.len() + 16);
+//!
+//! let mut decoder = ShareDecoder::<RcBytes>::new(&encoded);
+//! let b = decoder.read_str::<8>().unwrap();
+//! let c = decoder.read_str::<8>().unwrap();
+//! assert_eq!(b, a);
+//! assert_eq!(b.as_ptr(), c.as_ptr());
+//! ```
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use core::fmt;
+
+use crate::{Comrade, OurBytes, OurString};
+
+// value headers are a varint with the kind in the low 2 bits, followed by the length (or id) in the remaining bits
+const INLINE: u64 = 0;
+const SHARED: u64 = 1;
+const REFERENCE: u64 = 2;
+
+/// An error from decoding values via [`ShareDecoder`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShareError {
+    /// The input ended in the middle of a value.
+    UnexpectedEnd,
+    /// A value header was malformed.
+    InvalidHeader,
+    /// A value referred to a shared buffer that was not yet defined.
+    InvalidReference,
+    /// A string value was not valid UTF-8.
+    InvalidUtf8(core::str::Utf8Error),
+}
+
+impl fmt::Display for ShareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShareError::UnexpectedEnd => write!(f, "unexpected end of input"),
+            ShareError::InvalidHeader => write!(f, "invalid value header"),
+            ShareError::InvalidReference => write!(f, "invalid shared buffer reference"),
+            ShareError::InvalidUtf8(e) => write!(f, "invalid utf-8: {e}"),
+        }
+    }
+}
+
+impl core::error::Error for ShareError {}
+
+/// Encodes values, writing each unique shared buffer only once.
+///
+/// Shared handles are kept alive until the encoder is finished, so that their identities remain meaningful.
+pub struct ShareEncoder<T: Comrade + Clone> {
+    buf: Vec<u8>,
+    ids: BTreeMap<(usize, usize), u64>,
+    handles: Vec<T>,
+}
+
+impl<T: Comrade + Clone> ShareEncoder<T> {
+    /// Creates a new empty encoder.
+    pub fn new() -> Self {
+        Self { buf: Vec::new(), ids: BTreeMap::new(), handles: Vec::new() }
+    }
+    /// Writes a bytes value.
+    pub fn write_bytes<const N: usize>(&mut self, value: &OurBytes<T, N>) {
+        let Some(content) = value.as_comrade() else {
+            self.write_literal(INLINE, value);
+            return;
+        };
+        let key = content.identity();
+        match self.ids.get(&key) {
+            Some(id) => write_varint(&mut self.buf, (id << 2) | REFERENCE),
+            None => {
+                self.ids.insert(key, self.handles.len() as u64);
+                self.handles.push(content.clone());
+                self.write_literal(SHARED, value);
+            }
+        }
+    }
+    /// Writes a string value.
+    pub fn write_str<const N: usize>(&mut self, value: &OurString<T, N>) {
+        self.write_bytes(value.as_bytes_container())
+    }
+    /// Gets the encoded content.
+    pub fn finish(self) -> Vec<u8> {
+        self.buf
+    }
+    fn write_literal(&mut self, kind: u64, value: &[u8]) {
+        write_varint(&mut self.buf, ((value.len() as u64) << 2) | kind);
+        self.buf.extend_from_slice(value);
+    }
+}
+
+impl<T: Comrade + Clone> Default for ShareEncoder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decodes values written by [`ShareEncoder`], reconstructing shared handles.
+pub struct ShareDecoder<'a, T: Comrade + Clone> {
+    buf: &'a [u8],
+    handles: Vec<(T, bool)>, // (handle, known utf-8)
+}
+
+impl<'a, T: Comrade + Clone> ShareDecoder<'a, T> {
+    /// Creates a new decoder over the given encoded content.
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self { buf, handles: Vec::new() }
+    }
+    /// Checks if all values have been read.
+    pub fn is_finished(&self) -> bool {
+        self.buf.is_empty()
+    }
+    /// Reads a bytes value.
+    pub fn read_bytes<const N: usize>(&mut self) -> Result<OurBytes<T, N>, ShareError> {
+        self.read(false).map(|(x, _)| x)
+    }
+    /// Reads a string value.
+    ///
+    /// UTF-8 compliance is only checked once per unique shared buffer.
+    pub fn read_str<const N: usize>(&mut self) -> Result<OurString<T, N>, ShareError> {
+        let (value, id) = self.read::<N>(true)?;
+        match id.map(|id| &mut self.handles[id].1) {
+            Some(true) => Ok(unsafe { OurString::from_utf8_unchecked(value) }),
+            Some(valid) => {
+                let res = OurString::from_utf8(value).map_err(ShareError::InvalidUtf8)?;
+                *valid = true;
+                Ok(res)
+            }
+            None => OurString::from_utf8(value).map_err(ShareError::InvalidUtf8),
+        }
+    }
+    fn read<const N: usize>(&mut self, utf8: bool) -> Result<(OurBytes<T, N>, Option<usize>), ShareError> {
+        let header = read_varint(&mut self.buf)?;
+        match header & 3 {
+            INLINE => Ok((OurBytes::from(self.read_literal(header >> 2)?), None)),
+            SHARED => {
+                let content = self.read_literal(header >> 2)?;
+                if utf8 {
+                    core::str::from_utf8(content).map_err(ShareError::InvalidUtf8)?;
+                }
+                let content = T::from_slice(content);
+                self.handles.push((content.clone(), utf8));
+                Ok((OurBytes::from(content), Some(self.handles.len() - 1)))
+            }
+            REFERENCE => {
+                let id = usize::try_from(header >> 2).map_err(|_| ShareError::InvalidReference)?;
+                let (content, _) = self.handles.get(id).ok_or(ShareError::InvalidReference)?;
+                Ok((OurBytes::from(content.clone()), Some(id)))
+            }
+            _ => Err(ShareError::InvalidHeader),
+        }
+    }
+    fn read_literal(&mut self, len: u64) -> Result<&'a [u8], ShareError> {
+        let len = usize::try_from(len).map_err(|_| ShareError::UnexpectedEnd)?;
+        if len > self.buf.len() {
+            return Err(ShareError::UnexpectedEnd);
+        }
+        let (res, rest) = self.buf.split_at(len);
+        self.buf = rest;
+        Ok(res)
+    }
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn read_varint(buf: &mut &[u8]) -> Result<u64, ShareError> {
+    let mut res = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = buf.split_first().ok_or(ShareError::UnexpectedEnd)?;
+        *buf = rest;
+        res |= ((byte & 0x7f) as u64).checked_shl(shift).filter(|x| x >> shift == (byte & 0x7f) as u64).ok_or(ShareError::InvalidHeader)?;
+        if byte & 0x80 == 0 {
+            return Ok(res);
+        }
+    }
+    Err(ShareError::InvalidHeader)
+}
@@ -4,7 +4,7 @@ use core::borrow::Borrow;
 use core::ops::Deref;
 use core::hash::Hash;
 
-use crate::Comrade;
+use crate::{Comrade, ComradeFrom, StrComrade, WeakComrade};
 
 #[derive(Default, Clone)]
 struct ItsUtf8ISwear;
@@ -15,6 +15,9 @@ struct ItsUtf8ISwear;
 ///
 /// This type can be constructed via the [`From`] trait given a `&str` (in which case inlining is attempted but may result in a shared `T` allocation)
 /// or via [`OurString::from_utf8`] given the underlying shared [`OurBytes`](crate::OurBytes) container (in which case the shared handle is simply wrapped after checking UTF-8 compliance).
+/// If `T` is a [`StrComrade`] (such as [`ArcStr`](crate::comrades::ArcStr)), a shared `str` handle (such as `Arc<str>`) can also be wrapped directly via [`From`] without copying or checking UTF-8 compliance.
+///
+/// Comparing values which share the same content (e.g., clones) via [`PartialEq`], [`PartialOrd`] or [`Ord`] is `O(1)`, as the bytes are not compared at all (see [`ptr_eq`](Self::ptr_eq)).
 #[derive(Default, Clone)]
 pub struct OurString<T: Comrade, const N: usize>(crate::OurBytes<T, N>, ItsUtf8ISwear);
 
@@ -29,17 +32,115 @@ impl<T: Comrade, const N: usize> OurString<T, N> {
     /// Otherwise, re-inlining will be attempted, but may fail if `M < N` and result in a new shared `T` allocation.
     ///
     /// Because of this, it is advised to minimize the use of this function (e.g., by only using one [`OurString`] type throughout your codebase).
+    /// See also [`convert_compact`](Self::convert_compact) and [`try_convert`](Self::try_convert).
     pub fn convert<const M: usize>(self) -> OurString<T, M> {
         OurString(self.0.convert(), ItsUtf8ISwear)
     }
+    /// Like [`convert`](Self::convert), but re-inlines content which fits in `M` bytes even if it is currently stored by shared handle `T` (which is then dropped).
+    ///
+    /// This can free shared buffers which are no longer needed, but loses sharing with other handles to the same content.
+    pub fn convert_compact<const M: usize>(self) -> OurString<T, M> {
+        OurString(self.0.convert_compact(), ItsUtf8ISwear)
+    }
+    /// Like [`convert`](Self::convert), but fails (returning the original value) instead of making a new shared `T` allocation,
+    /// which happens if the content is inlined but does not fit in `M` bytes.
+    pub fn try_convert<const M: usize>(self) -> Result<OurString<T, M>, Self> {
+        match self.0.try_convert() {
+            Ok(res) => Ok(OurString(res, ItsUtf8ISwear)),
+            Err(original) => Err(OurString(original, ItsUtf8ISwear)),
+        }
+    }
+    /// Converts this [`OurString`] instance into another [`OurString`] type which uses a different shared type `U`.
+    ///
+    /// If the content of this instance is already allocated via shared handle `T`, it will be converted via [`ComradeFrom`] (which may reuse the allocation) without inlining.
+    /// Otherwise, re-inlining will be attempted, but may fail if `M < N` and result in a new shared `U` allocation.
+    pub fn convert_comrade<U: ComradeFrom<T>, const M: usize>(self) -> OurString<U, M> {
+        OurString(self.0.convert_comrade(), ItsUtf8ISwear)
+    }
+    /// Wraps a shared `str` handle without copying or checking UTF-8 compliance (this is also available via [`From`]).
+    pub fn from_str_handle(content: T::Str) -> Self where T: StrComrade {
+        Self(crate::OurBytes::from(T::from_str_handle(content)), ItsUtf8ISwear)
+    }
+    /// Inlines the content (and drops the handle) if it fits in `N` bytes, and otherwise wraps the shared handle.
+    ///
+    /// The result is always in canonical form (see [`is_canonical`](Self::is_canonical)).
+    pub fn from_comrade_inlining(content: T::Str) -> Self where T: StrComrade {
+        Self(crate::OurBytes::from_comrade_inlining(T::from_str_handle(content)), ItsUtf8ISwear)
+    }
+    /// Re-inlines the content (dropping the shared handle) if it is stored by shared handle `T` but fits in `N` bytes.
+    ///
+    /// Afterwards, this value is in canonical form (see [`is_canonical`](Self::is_canonical)).
+    pub fn normalize(&mut self) {
+        self.0.normalize()
+    }
+    /// Checks if this value is in canonical form, meaning that content is inlined if and only if it fits in `N` bytes.
+    pub fn is_canonical(&self) -> bool {
+        self.0.is_canonical()
+    }
+    /// Asserts that this value is in canonical form (see [`is_canonical`](Self::is_canonical)), but only in debug builds.
+    #[track_caller]
+    pub fn debug_assert_canonical(&self) {
+        debug_assert!(self.is_canonical(), "value is not in canonical form");
+    }
     /// Gets a shared reference to the content.
     pub fn as_str(&self) -> &str {
         self
     }
+    /// Checks if the content is stored inline (rather than by shared handle `T`).
+    pub fn is_inline(&self) -> bool {
+        self.0.is_inline()
+    }
+    /// Gets the max number of bytes which can be stored inline, which is `N` capped at 254.
+    pub const fn inline_capacity(&self) -> usize {
+        self.0.inline_capacity()
+    }
+    /// Gets the number of strong handles to the shared content (see [`Comrade::strong_count`]).
+    ///
+    /// This is `None` if the content is inlined (and thus not shared) or if `T` does not support it.
+    pub fn strong_count(&self) -> Option<usize> {
+        self.0.strong_count()
+    }
+    /// Gets the number of heap bytes used by the content, which is zero if it is inlined.
+    ///
+    /// Shared content is counted in full (see [`HeapUsage`](crate::HeapUsage) for de-duplicating shared content across many values).
+    /// If `T` does not support [`Comrade::heap_size`], this is just the size of the content itself.
+    pub fn heap_size(&self) -> usize {
+        self.0.heap_size()
+    }
+    /// Checks if both values are stored by shared handles to the same content (see [`Comrade::ptr_eq`]).
+    ///
+    /// Inlined content is never shared, so this is always `false` if either value is inlined.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.0.ptr_eq(&other.0)
+    }
+    /// Gets a shared reference to the shared handle `T`, or `None` if the content is inlined.
+    pub fn as_comrade(&self) -> Option<&T> {
+        self.0.as_comrade()
+    }
+    /// Extracts the shared handle `T` without copying, or fails (returning the original value) if the content is inlined.
+    pub fn into_comrade(self) -> Result<T, Self> {
+        self.0.into_comrade().map_err(|x| OurString(x, ItsUtf8ISwear))
+    }
+    /// Extracts the shared handle `T`, copying inlined content into a new shared `T` allocation if needed.
+    pub fn into_comrade_or_alloc(self) -> T {
+        self.0.into_comrade_or_alloc()
+    }
+    /// Gets a shared reference to the underlying shared bytes container.
+    pub(crate) fn as_bytes_container(&self) -> &crate::OurBytes<T, N> {
+        &self.0
+    }
     /// Extracts the underlying shared bytes container.
     pub fn into_bytes(self) -> crate::OurBytes<T, N> {
         self.0
     }
+    /// Constructs a new [`OurString`] instance from the underlying shared bytes container without checking UTF-8 compliance.
+    ///
+    /// # Safety
+    ///
+    /// The content must be valid UTF-8.
+    pub(crate) unsafe fn from_utf8_unchecked(value: crate::OurBytes<T, N>) -> Self {
+        Self(value, ItsUtf8ISwear)
+    }
     /// Attempts to construct a new [`OurString`] instance from the underlying shared bytes container.
     pub fn from_utf8(value: crate::OurBytes<T, N>) -> Result<Self, core::str::Utf8Error> {
         core::str::from_utf8(&value)?;
@@ -47,6 +148,36 @@ impl<T: Comrade, const N: usize> OurString<T, N> {
     }
 }
 
+impl<T: WeakComrade, const N: usize> OurString<T, N> {
+    /// Creates a new [`OurWeakString`] handle to the same content.
+    ///
+    /// Inlined content is simply copied into the weak handle, so upgrading it will always succeed.
+    pub fn downgrade(&self) -> OurWeakString<T, N> {
+        OurWeakString(self.0.downgrade(), ItsUtf8ISwear)
+    }
+}
+
+/// A weak handle to the content of an [`OurString`] which does not keep shared content alive.
+///
+/// This type can be constructed via [`OurString::downgrade`].
+#[derive(Clone)]
+pub struct OurWeakString<T: WeakComrade, const N: usize>(crate::OurWeakBytes<T, N>, ItsUtf8ISwear);
+
+impl<T: WeakComrade, const N: usize> OurWeakString<T, N> {
+    /// Attempts to upgrade this handle into an [`OurString`], which fails if the shared content has already been dropped.
+    ///
+    /// This always succeeds for inlined content.
+    pub fn upgrade(&self) -> Option<OurString<T, N>> {
+        self.0.upgrade().map(|x| OurString(x, ItsUtf8ISwear))
+    }
+}
+
+impl<T: WeakComrade, const N: usize> Debug for OurWeakString<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(Weak)")
+    }
+}
+
 impl<T: Comrade, const N: usize> Deref for OurString<T, N> {
     type Target = str;
     fn deref(&self) -> &Self::Target {
@@ -92,13 +223,13 @@ impl<T: Comrade, const N: usize> Hash for OurString<T, N> {
 
 impl<U: Deref<Target = str>, T: Comrade, const N: usize> PartialEq<U> for OurString<T, N> {
     fn eq(&self, other: &U) -> bool {
-        (**self).eq(&**other)
+        core::ptr::eq(&**self, &**other) || (**self).eq(&**other)
     }
 }
 
 impl<T: Comrade, const N: usize> PartialEq<OurString<T, N>> for &str {
     fn eq(&self, other: &OurString<T, N>) -> bool {
-        (**self).eq(&**other)
+        core::ptr::eq(*self, &**other) || (**self).eq(&**other)
     }
 }
 
@@ -106,18 +237,27 @@ impl<T: Comrade, const N: usize> Eq for OurString<T, N> {}
 
 impl<U: Deref<Target = str>, T: Comrade, const N: usize> PartialOrd<U> for OurString<T, N> {
     fn partial_cmp(&self, other: &U) -> Option<Ordering> {
+        if core::ptr::eq(&**self, &**other) {
+            return Some(Ordering::Equal);
+        }
         (**self).partial_cmp(&**other)
     }
 }
 
 impl<T: Comrade, const N: usize> PartialOrd<OurString<T, N>> for &str {
     fn partial_cmp(&self, other: &OurString<T, N>) -> Option<Ordering> {
+        if core::ptr::eq(*self, &**other) {
+            return Some(Ordering::Equal);
+        }
         (**self).partial_cmp(&**other)
     }
 }
 
 impl<T: Comrade, const N: usize> Ord for OurString<T, N> {
     fn cmp(&self, other: &Self) -> Ordering {
+        if self.ptr_eq(other) {
+            return Ordering::Equal;
+        }
         (**self).cmp(&**other)
     }
 }
@@ -0,0 +1,186 @@
+//! A conformance test suite for custom [`Comrade`] implementations.
+//!
+//! The easiest way to use this is via the [`comrade_conformance!`](crate::comrade_conformance) macro,
+//! which generates a set of `#[test]` functions for a given [`Comrade`] type.
+//! Alternatively, the individual checks can be called directly.
+//!
+//! These checks cannot detect memory errors (like leaks or double frees) on their own, so consider running them under Miri as well.
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::string::String;
+use std::vec::Vec;
+
+use proptest::test_runner::TestRunner;
+use proptest::prelude::*;
+
+use crate::{Comrade, OurBytes};
+
+fn hash<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut h = DefaultHasher::new();
+    value.hash(&mut h);
+    h.finish()
+}
+
+/// Gets sample content with lengths around common inline/outline boundaries.
+pub fn samples() -> Vec<Vec<u8>> {
+    [0, 1, 2, 3, 4, 7, 8, 9, 15, 16, 17, 22, 23, 24, 31, 32, 33, 100, 253, 254, 255, 256, 1000]
+        .into_iter().map(|len| (0..len).map(|i| b'a' + (i % 26) as u8).collect()).collect()
+}
+
+/// Checks that `T` round-trips content, and that clones share the same (stable) buffer which outlives the original handle.
+pub fn check_comrade<T: Comrade + Clone>(value: &[u8]) {
+    let a = T::from_slice(value);
+    assert_eq!(a.as_slice(), value, "from_slice must preserve content");
+    assert_eq!(a.as_slice().as_ptr(), a.as_slice().as_ptr(), "as_slice must be stable");
+
+    let clones = (0..8).map(|_| a.clone()).collect::<Vec<_>>();
+    for clone in clones.iter() {
+        assert_eq!(clone.as_slice(), value, "clone must preserve content");
+        assert_eq!(clone.as_slice().as_ptr(), a.as_slice().as_ptr(), "clone must share the buffer");
+        assert!(clone.ptr_eq(&a) && a.ptr_eq(clone), "clones must be ptr_eq");
+        assert_eq!(clone.identity(), a.identity(), "clones must have the same identity");
+    }
+    let other = T::from_slice(value);
+    assert_eq!(other.ptr_eq(&a), other.identity() == a.identity(), "ptr_eq must agree with identity");
+    drop(other);
+    if let Some(count) = a.strong_count() {
+        assert_eq!(count, clones.len() + 1, "strong_count must count all handles");
+    }
+    if let Some(size) = a.heap_size() {
+        assert!(size >= size_of_val(value), "heap_size must include the content");
+    }
+    let ptr = a.as_slice().as_ptr();
+    drop(a);
+    for (i, clone) in clones.into_iter().enumerate() {
+        assert_eq!(clone.as_slice(), value, "clones must outlive the original handle");
+        assert_eq!(clone.as_slice().as_ptr(), ptr, "as_slice must be stable after dropping other handles");
+        if i % 2 == 0 {
+            drop(clone.clone());
+        }
+    }
+}
+
+/// Checks that [`OurBytes`] inlines exactly the content which fits in `N` bytes, and that wrapping a `T` handle does not inline or copy.
+pub fn check_inline_outline<T: Comrade + Clone, const N: usize>(value: &[u8]) {
+    let a = OurBytes::<T, N>::from(value);
+    assert_eq!(a, value);
+    assert_eq!(a.is_inline(), value.len() <= N && value.len() < 255, "content must be inlined iff it fits");
+
+    let shared = T::from_slice(value);
+    let b = OurBytes::<T, N>::from(shared.clone());
+    assert_eq!(b, value);
+    assert!(!b.is_inline(), "wrapping a handle must not inline");
+    assert_eq!(b.as_ptr(), shared.as_slice().as_ptr(), "wrapping a handle must not copy");
+}
+
+/// Checks that clones of [`OurBytes`] have the same content, and share the buffer for outlined content.
+pub fn check_clone<T: Comrade + Clone, const N: usize>(value: &[u8]) {
+    let a = OurBytes::<T, N>::from(value);
+    let b = a.clone();
+    assert_eq!(a, b);
+    assert_eq!(b, value);
+    assert_eq!(a.as_ptr() == b.as_ptr(), !a.is_inline(), "clones must share outlined content");
+    drop(a);
+    assert_eq!(b, value, "clones must outlive the original");
+}
+
+/// Checks that [`OurBytes`] hashes the same as its content, regardless of representation.
+pub fn check_hash<T: Comrade, const N: usize>(value: &[u8]) {
+    let a = OurBytes::<T, N>::from(value);
+    let b = OurBytes::<T, N>::from(T::from_slice(value));
+    assert_eq!(hash(&a), hash(value));
+    assert_eq!(hash(&b), hash(value));
+}
+
+/// Checks that [`OurBytes`] compares the same as its content, regardless of representation.
+pub fn check_ordering<T: Comrade, const N: usize>(a: &[u8], b: &[u8]) {
+    let x = OurBytes::<T, N>::from(a);
+    let y = OurBytes::<T, N>::from(T::from_slice(b));
+    assert_eq!(x == y, a == b);
+    assert_eq!(x.cmp(&y), a.cmp(b));
+    assert_eq!(y.cmp(&x), b.cmp(a));
+    assert_eq!(x.partial_cmp(&y), a.partial_cmp(b));
+}
+
+/// Checks that [`convert`](OurBytes::convert) preserves content, and reuses the shared handle for outlined content.
+pub fn check_convert<T: Comrade + Clone, const N: usize>(value: &[u8]) {
+    fn convert<T: Comrade + Clone, const N: usize, const M: usize>(a: &OurBytes<T, N>, value: &[u8]) {
+        let b = a.clone().convert::<M>();
+        assert_eq!(b, value);
+        if !a.is_inline() {
+            assert_eq!(b.as_ptr(), a.as_ptr(), "convert must reuse shared handles");
+        }
+    }
+    let a = OurBytes::<T, N>::from(value);
+    convert::<T, N, 0>(&a, value);
+    convert::<T, N, 4>(&a, value);
+    convert::<T, N, 16>(&a, value);
+    convert::<T, N, 300>(&a, value);
+}
+
+/// Runs all checks on all of the given samples (and pairs thereof).
+pub fn check_all<T: Comrade + Clone, const N: usize>(values: &[Vec<u8>]) {
+    for value in values {
+        check_comrade::<T>(value);
+        check_inline_outline::<T, N>(value);
+        check_clone::<T, N>(value);
+        check_hash::<T, N>(value);
+        check_convert::<T, N>(value);
+        for other in values {
+            check_ordering::<T, N>(value, other);
+        }
+    }
+}
+
+/// Runs all checks on random content via proptest.
+pub fn check_all_proptest<T: Comrade + Clone, const N: usize>() {
+    let strategy = (any::<String>(), any::<String>());
+    TestRunner::default().run(&strategy, |(a, b)| {
+        check_all::<T, N>(&[a.into_bytes(), b.into_bytes()]);
+        Ok(())
+    }).unwrap();
+}
+
+/// Generates a module of `#[test]` functions which run the [`testing`](crate::testing) conformance suite against a [`Comrade`] type.
+///
+/// ```
+/// # use our_string::comrade_conformance;
+/// comrade_conformance!(rc_slice: std::rc::Rc<[u8]>);
+/// ```
+#[macro_export]
+macro_rules! comrade_conformance {
+    ($name:ident : $t:ty) => {
+        #[allow(unused_imports)]
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn comrade() {
+                for value in $crate::testing::samples() {
+                    $crate::testing::check_comrade::<$t>(&value);
+                }
+            }
+            #[test]
+            fn inline_outline() {
+                for value in $crate::testing::samples() {
+                    $crate::testing::check_inline_outline::<$t, 0>(&value);
+                    $crate::testing::check_inline_outline::<$t, 8>(&value);
+                    $crate::testing::check_inline_outline::<$t, 23>(&value);
+                    $crate::testing::check_inline_outline::<$t, 300>(&value);
+                }
+            }
+            #[test]
+            fn all() {
+                $crate::testing::check_all::<$t, 0>(&$crate::testing::samples());
+                $crate::testing::check_all::<$t, 8>(&$crate::testing::samples());
+                $crate::testing::check_all::<$t, 23>(&$crate::testing::samples());
+            }
+            #[test]
+            #[cfg(not(miri))]
+            fn proptest() {
+                $crate::testing::check_all_proptest::<$t, 0>();
+                $crate::testing::check_all_proptest::<$t, 15>();
+            }
+        }
+    };
+}
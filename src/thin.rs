@@ -0,0 +1,258 @@
+use core::cmp::{Ordering, PartialEq, Eq, PartialOrd, Ord};
+use core::fmt::{self, Debug, Display};
+use core::mem::ManuallyDrop;
+use core::marker::PhantomData;
+use core::borrow::Borrow;
+use core::ptr::NonNull;
+use core::ops::Deref;
+use core::hash::Hash;
+
+use crate::ThinComrade;
+
+const WORD: usize = size_of::<usize>();
+const START: usize = if cfg!(target_endian = "little") { 1 } else { 0 }; // skip the low (tag) byte
+
+/// A pointer-sized immutable shared byte collection.
+///
+/// Data is backed inline up to `size_of::<usize>() - 1` bytes (7 on 64-bit targets), or stored dynamically by (shared) [`ThinComrade`] `T`.
+/// The low bit of the word marks inlined content, which is stored in the remaining bytes of the word itself.
+///
+/// This type can be constructed via the [`From`] trait given either a `&[u8]` (in which case inlining is attempted but may result in a shared `T` allocation)
+/// or a shared handle of type `T` (in which case no inlining is used and the shared handle is simply wrapped).
+pub struct ThinOurBytes<T: ThinComrade>(NonNull<u8>, PhantomData<T>);
+
+impl<T: ThinComrade> ThinOurBytes<T> {
+    /// Creates a new empty instance of [`ThinOurBytes`] with inlined data.
+    pub const fn new() -> Self {
+        Self(unsafe { NonNull::new_unchecked(core::ptr::without_provenance_mut(1)) }, PhantomData)
+    }
+    /// Gets a shared reference to the content.
+    pub fn as_slice(&self) -> &[u8] {
+        self
+    }
+    fn inline_len(&self) -> Option<usize> {
+        let tag = self.0.as_ptr().addr() as u8;
+        (tag & 1 != 0).then_some((tag >> 1) as usize)
+    }
+    fn with_outline<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        f(&ManuallyDrop::new(unsafe { T::from_raw(self.0) }))
+    }
+}
+
+impl<T: ThinComrade> Deref for ThinOurBytes<T> {
+    type Target = [u8];
+    fn deref(&self) -> &Self::Target {
+        match self.inline_len() {
+            Some(len) => unsafe { core::slice::from_raw_parts((&self.0 as *const NonNull<u8> as *const u8).add(START), len) },
+            // the content lives in the shared allocation (see the ThinComrade safety contract), so it outlives the temporary handle
+            None => self.with_outline(|content| unsafe { &*(content.as_slice() as *const [u8]) }),
+        }
+    }
+}
+
+impl<T: ThinComrade> From<&[u8]> for ThinOurBytes<T> {
+    fn from(value: &[u8]) -> Self {
+        if value.len() < WORD {
+            let mut content = [0; WORD];
+            content[START..START + value.len()].copy_from_slice(value);
+            let word = usize::from_ne_bytes(content) | (value.len() << 1) | 1;
+            Self(unsafe { NonNull::new_unchecked(core::ptr::without_provenance_mut(word)) }, PhantomData)
+        } else {
+            Self::from(T::from_slice(value))
+        }
+    }
+}
+
+impl<T: ThinComrade> From<T> for ThinOurBytes<T> {
+    fn from(content: T) -> Self {
+        let ptr = content.into_raw();
+        debug_assert!(ptr.as_ptr().addr() & 1 == 0);
+        Self(ptr, PhantomData)
+    }
+}
+
+impl<T: ThinComrade> Clone for ThinOurBytes<T> {
+    fn clone(&self) -> Self {
+        match self.inline_len() {
+            Some(_) => Self(self.0, PhantomData),
+            None => Self::from(self.with_outline(T::clone)),
+        }
+    }
+}
+
+impl<T: ThinComrade> Drop for ThinOurBytes<T> {
+    fn drop(&mut self) {
+        if self.inline_len().is_none() {
+            drop(unsafe { T::from_raw(self.0) });
+        }
+    }
+}
+
+unsafe impl<T: ThinComrade + Send + Sync> Send for ThinOurBytes<T> {}
+unsafe impl<T: ThinComrade + Send + Sync> Sync for ThinOurBytes<T> {}
+
+impl<T: ThinComrade> Default for ThinOurBytes<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ThinComrade> AsRef<[u8]> for ThinOurBytes<T> {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl<T: ThinComrade> Borrow<[u8]> for ThinOurBytes<T> {
+    fn borrow(&self) -> &[u8] {
+        self
+    }
+}
+
+impl<T: ThinComrade> Debug for ThinOurBytes<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <[u8] as Debug>::fmt(&**self, f)
+    }
+}
+
+impl<T: ThinComrade> Hash for ThinOurBytes<T> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl<U: Deref<Target = [u8]>, T: ThinComrade> PartialEq<U> for ThinOurBytes<T> {
+    fn eq(&self, other: &U) -> bool {
+        (**self).eq(&**other)
+    }
+}
+
+impl<T: ThinComrade> PartialEq<ThinOurBytes<T>> for &[u8] {
+    fn eq(&self, other: &ThinOurBytes<T>) -> bool {
+        (**self).eq(&**other)
+    }
+}
+
+impl<T: ThinComrade> Eq for ThinOurBytes<T> {}
+
+impl<U: Deref<Target = [u8]>, T: ThinComrade> PartialOrd<U> for ThinOurBytes<T> {
+    fn partial_cmp(&self, other: &U) -> Option<Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<T: ThinComrade> PartialOrd<ThinOurBytes<T>> for &[u8] {
+    fn partial_cmp(&self, other: &ThinOurBytes<T>) -> Option<Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<T: ThinComrade> Ord for ThinOurBytes<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+/// A pointer-sized immutable shared string.
+///
+/// Data is backed inline up to `size_of::<usize>() - 1` bytes (7 on 64-bit targets), or stored dynamically by (shared) [`ThinComrade`] `T`.
+///
+/// This type can be constructed via the [`From`] trait given a `&str` (in which case inlining is attempted but may result in a shared `T` allocation)
+/// or via [`ThinOurString::from_utf8`] given the underlying shared [`ThinOurBytes`] container (in which case the shared handle is simply wrapped after checking UTF-8 compliance).
+#[derive(Default, Clone)]
+pub struct ThinOurString<T: ThinComrade>(ThinOurBytes<T>);
+
+impl<T: ThinComrade> ThinOurString<T> {
+    /// Creates a new empty instance of [`ThinOurString`] with inlined data.
+    pub const fn new() -> Self {
+        Self(ThinOurBytes::new())
+    }
+    /// Gets a shared reference to the content.
+    pub fn as_str(&self) -> &str {
+        self
+    }
+    /// Extracts the underlying shared bytes container.
+    pub fn into_bytes(self) -> ThinOurBytes<T> {
+        self.0
+    }
+    /// Attempts to construct a new [`ThinOurString`] instance from the underlying shared bytes container.
+    pub fn from_utf8(value: ThinOurBytes<T>) -> Result<Self, core::str::Utf8Error> {
+        core::str::from_utf8(&value)?;
+        Ok(Self(value))
+    }
+}
+
+impl<T: ThinComrade> Deref for ThinOurString<T> {
+    type Target = str;
+    fn deref(&self) -> &Self::Target {
+        unsafe { core::str::from_utf8_unchecked(&self.0) }
+    }
+}
+
+impl<T: ThinComrade> From<&str> for ThinOurString<T> {
+    fn from(value: &str) -> Self {
+        Self(value.as_bytes().into())
+    }
+}
+
+impl<T: ThinComrade> AsRef<str> for ThinOurString<T> {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl<T: ThinComrade> Borrow<str> for ThinOurString<T> {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl<T: ThinComrade> Debug for ThinOurString<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <str as Debug>::fmt(&**self, f)
+    }
+}
+
+impl<T: ThinComrade> Display for ThinOurString<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <str as Display>::fmt(&**self, f)
+    }
+}
+
+impl<T: ThinComrade> Hash for ThinOurString<T> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl<U: Deref<Target = str>, T: ThinComrade> PartialEq<U> for ThinOurString<T> {
+    fn eq(&self, other: &U) -> bool {
+        (**self).eq(&**other)
+    }
+}
+
+impl<T: ThinComrade> PartialEq<ThinOurString<T>> for &str {
+    fn eq(&self, other: &ThinOurString<T>) -> bool {
+        (**self).eq(&**other)
+    }
+}
+
+impl<T: ThinComrade> Eq for ThinOurString<T> {}
+
+impl<U: Deref<Target = str>, T: ThinComrade> PartialOrd<U> for ThinOurString<T> {
+    fn partial_cmp(&self, other: &U) -> Option<Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<T: ThinComrade> PartialOrd<ThinOurString<T>> for &str {
+    fn partial_cmp(&self, other: &ThinOurString<T>) -> Option<Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<T: ThinComrade> Ord for ThinOurString<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (**self).cmp(&**other)
+    }
+}
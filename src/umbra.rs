@@ -0,0 +1,293 @@
+use core::cmp::{Ordering, PartialEq, Eq, PartialOrd, Ord};
+use core::fmt::{self, Debug, Display};
+use core::mem::ManuallyDrop;
+use core::borrow::Borrow;
+use core::ops::Deref;
+use core::hash::Hash;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::Comrade;
+
+const PREFIX: usize = 4;
+const INLINE: usize = 12;
+
+#[repr(C)]
+union Rest<T> {
+    inline: [u8; INLINE - PREFIX],
+    outline: ManuallyDrop<T>,
+}
+
+/// A customizable immutable shared byte collection with an inline length and prefix for fast comparisons.
+///
+/// This is laid out like the strings from Umbra (a.k.a. "German strings"): the length and first 4 bytes are always stored in the struct,
+/// followed by either the remaining 8 bytes of inlined content (up to 12 bytes total) or the (shared) [`Comrade`] `T`.
+/// With `T` being [`RcBytes`](crate::comrades::RcBytes) or [`ArcBytes`](crate::comrades::ArcBytes), this is 16 bytes.
+///
+/// Because of this, equality and ordering can reject most mismatches without dereferencing `T`.
+/// To preserve this, only comparisons against `Self`, byte slices, and `Vec<u8>` are supported.
+///
+/// This type can be constructed via the [`From`] trait given either a `&[u8]` (in which case inlining is attempted but may result in a shared `T` allocation)
+/// or a shared handle of type `T` (in which case the shared handle is simply wrapped, unless the content is small enough to be inlined).
+#[repr(C)]
+pub struct UmbraOurBytes<T: Comrade> {
+    len: u32, // saturates at u32::MAX, in which case the real length is taken from `T`
+    prefix: [u8; PREFIX],
+    rest: Rest<T>,
+}
+
+impl<T: Comrade> UmbraOurBytes<T> {
+    /// Creates a new empty instance of [`UmbraOurBytes`] with inlined data.
+    pub const fn new() -> Self {
+        const { assert!(align_of::<T>() <= 8, "UmbraOurBytes requires a comrade with alignment of at most 8") }
+        Self { len: 0, prefix: [0; PREFIX], rest: Rest { inline: [0; INLINE - PREFIX] } }
+    }
+    /// Gets a shared reference to the content.
+    pub fn as_slice(&self) -> &[u8] {
+        self
+    }
+    fn is_inline(&self) -> bool {
+        self.len as usize <= INLINE
+    }
+    fn head(&self) -> (u32, [u8; PREFIX]) {
+        (self.len, self.prefix)
+    }
+}
+
+impl<T: Comrade> Deref for UmbraOurBytes<T> {
+    type Target = [u8];
+    fn deref(&self) -> &Self::Target {
+        match self.is_inline() {
+            true => unsafe { core::slice::from_raw_parts((self as *const Self as *const u8).add(core::mem::offset_of!(Self, prefix)), self.len as usize) },
+            false => unsafe { self.rest.outline.as_slice() },
+        }
+    }
+}
+
+impl<T: Comrade> From<&[u8]> for UmbraOurBytes<T> {
+    fn from(value: &[u8]) -> Self {
+        if value.len() <= INLINE {
+            let mut res = Self::new();
+            let mut content = [0; INLINE];
+            content[..value.len()].copy_from_slice(value);
+            res.len = value.len() as u32;
+            res.prefix.copy_from_slice(&content[..PREFIX]);
+            res.rest.inline = content[PREFIX..].try_into().unwrap();
+            res
+        } else {
+            Self::from(T::from_slice(value))
+        }
+    }
+}
+
+impl<T: Comrade> From<T> for UmbraOurBytes<T> {
+    fn from(content: T) -> Self {
+        let value = content.as_slice();
+        if value.len() <= INLINE {
+            return Self::from(value);
+        }
+        let mut prefix = [0; PREFIX];
+        prefix.copy_from_slice(&value[..PREFIX]);
+        Self { len: u32::try_from(value.len()).unwrap_or(u32::MAX), prefix, rest: Rest { outline: ManuallyDrop::new(content) } }
+    }
+}
+
+impl<T: Comrade + Clone> Clone for UmbraOurBytes<T> {
+    fn clone(&self) -> Self {
+        let rest = match self.is_inline() {
+            true => Rest { inline: unsafe { self.rest.inline } },
+            false => Rest { outline: unsafe { self.rest.outline.clone() } },
+        };
+        Self { len: self.len, prefix: self.prefix, rest }
+    }
+}
+
+impl<T: Comrade> Drop for UmbraOurBytes<T> {
+    fn drop(&mut self) {
+        if !self.is_inline() {
+            unsafe { ManuallyDrop::drop(&mut self.rest.outline) }
+        }
+    }
+}
+
+impl<T: Comrade> Default for UmbraOurBytes<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Comrade> AsRef<[u8]> for UmbraOurBytes<T> {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl<T: Comrade> Borrow<[u8]> for UmbraOurBytes<T> {
+    fn borrow(&self) -> &[u8] {
+        self
+    }
+}
+
+impl<T: Comrade> Debug for UmbraOurBytes<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <[u8] as Debug>::fmt(&**self, f)
+    }
+}
+
+impl<T: Comrade> Hash for UmbraOurBytes<T> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl<T: Comrade> PartialEq for UmbraOurBytes<T> {
+    fn eq(&self, other: &Self) -> bool {
+        if self.head() != other.head() {
+            return false;
+        }
+        match self.is_inline() {
+            true => unsafe { self.rest.inline == other.rest.inline },
+            false => (**self).eq(&**other),
+        }
+    }
+}
+
+impl<T: Comrade> Eq for UmbraOurBytes<T> {}
+
+impl<T: Comrade> PartialOrd for UmbraOurBytes<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Comrade> Ord for UmbraOurBytes<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // prefixes are zero-padded, so a mismatch is decisive, and a match with a short side means that side is a prefix of the other
+        match u32::from_be_bytes(self.prefix).cmp(&u32::from_be_bytes(other.prefix)) {
+            Ordering::Equal if self.len as usize <= PREFIX || other.len as usize <= PREFIX => self.len.cmp(&other.len),
+            Ordering::Equal => (**self).cmp(&**other),
+            x => x,
+        }
+    }
+}
+
+macro_rules! impl_slice_comparisons {
+    ($name:ident : $target:ty => $($other:ty),*) => {$(
+        impl<T: Comrade> PartialEq<$other> for $name<T> {
+            fn eq(&self, other: &$other) -> bool {
+                <$target>::eq(&**self, AsRef::<$target>::as_ref(other))
+            }
+        }
+        impl<T: Comrade> PartialEq<$name<T>> for $other {
+            fn eq(&self, other: &$name<T>) -> bool {
+                <$target>::eq(AsRef::<$target>::as_ref(self), &**other)
+            }
+        }
+        impl<T: Comrade> PartialOrd<$other> for $name<T> {
+            fn partial_cmp(&self, other: &$other) -> Option<Ordering> {
+                <$target>::partial_cmp(&**self, AsRef::<$target>::as_ref(other))
+            }
+        }
+        impl<T: Comrade> PartialOrd<$name<T>> for $other {
+            fn partial_cmp(&self, other: &$name<T>) -> Option<Ordering> {
+                <$target>::partial_cmp(AsRef::<$target>::as_ref(self), &**other)
+            }
+        }
+    )*};
+}
+impl_slice_comparisons! { UmbraOurBytes : [u8] => [u8], &[u8], Vec<u8> }
+impl_slice_comparisons! { UmbraOurString : str => str, &str, String }
+
+/// A customizable immutable shared string with an inline length and prefix for fast comparisons.
+///
+/// This has the same layout as [`UmbraOurBytes`], which it wraps.
+/// Likewise, only comparisons against `Self`, string slices, and `String` are supported.
+///
+/// This type can be constructed via the [`From`] trait given a `&str` (in which case inlining is attempted but may result in a shared `T` allocation)
+/// or via [`UmbraOurString::from_utf8`] given the underlying shared [`UmbraOurBytes`] container (in which case the shared handle is simply wrapped after checking UTF-8 compliance).
+#[derive(Default, Clone)]
+pub struct UmbraOurString<T: Comrade>(UmbraOurBytes<T>);
+
+impl<T: Comrade> UmbraOurString<T> {
+    /// Creates a new empty instance of [`UmbraOurString`] with inlined data.
+    pub const fn new() -> Self {
+        Self(UmbraOurBytes::new())
+    }
+    /// Gets a shared reference to the content.
+    pub fn as_str(&self) -> &str {
+        self
+    }
+    /// Extracts the underlying shared bytes container.
+    pub fn into_bytes(self) -> UmbraOurBytes<T> {
+        self.0
+    }
+    /// Attempts to construct a new [`UmbraOurString`] instance from the underlying shared bytes container.
+    pub fn from_utf8(value: UmbraOurBytes<T>) -> Result<Self, core::str::Utf8Error> {
+        core::str::from_utf8(&value)?;
+        Ok(Self(value))
+    }
+}
+
+impl<T: Comrade> PartialEq for UmbraOurString<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq(&other.0)
+    }
+}
+
+impl<T: Comrade> Eq for UmbraOurString<T> {}
+
+impl<T: Comrade> PartialOrd for UmbraOurString<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Comrade> Ord for UmbraOurString<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<T: Comrade> Deref for UmbraOurString<T> {
+    type Target = str;
+    fn deref(&self) -> &Self::Target {
+        unsafe { core::str::from_utf8_unchecked(&self.0) }
+    }
+}
+
+impl<T: Comrade> From<&str> for UmbraOurString<T> {
+    fn from(value: &str) -> Self {
+        Self(value.as_bytes().into())
+    }
+}
+
+impl<T: Comrade> AsRef<str> for UmbraOurString<T> {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl<T: Comrade> Borrow<str> for UmbraOurString<T> {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl<T: Comrade> Debug for UmbraOurString<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <str as Debug>::fmt(&**self, f)
+    }
+}
+
+impl<T: Comrade> Display for UmbraOurString<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <str as Display>::fmt(&**self, f)
+    }
+}
+
+impl<T: Comrade> Hash for UmbraOurString<T> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
@@ -0,0 +1,97 @@
+#![cfg(any(feature = "arbitrary", feature = "proptest"))]
+
+use std::rc::Rc;
+
+use our_string::{OurString, OurBytes};
+use our_string::comrades::{RcBytes, ArcBytes, RcStr};
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn test_arbitrary() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    let (mut boundary, mut inline, mut outline, mut wrapped) = (0, 0, 0, 0);
+    for seed in 0..512u32 {
+        let data = (0..256u32).map(|i| (seed.wrapping_mul(2654435761).wrapping_add(i.wrapping_mul(40503)) >> 7) as u8).collect::<Vec<_>>();
+        let v = OurBytes::<RcBytes, 8>::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        boundary += (7..=9).contains(&v.len()) as usize;
+        inline += v.is_inline() as usize;
+        outline += (!v.is_inline() && v.len() > 8) as usize;
+        wrapped += (!v.is_inline() && v.len() <= 8) as usize;
+
+        let s = OurString::<Rc<[u8]>, 8>::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        assert!(std::str::from_utf8(s.as_bytes()).is_ok());
+
+        let _ = OurBytes::<RcStr, 8>::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        let _ = RcBytes::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        let _ = ArcBytes::arbitrary(&mut Unstructured::new(&data)).unwrap();
+    }
+    assert!(boundary > 0 && inline > 0 && outline > 0 && wrapped > 0, "{boundary} {inline} {outline} {wrapped}");
+}
+
+#[cfg(feature = "proptest")]
+#[test]
+fn test_proptest() {
+    use proptest::strategy::{Strategy, ValueTree};
+    use proptest::test_runner::TestRunner;
+    use proptest::arbitrary::any;
+
+    let mut runner = TestRunner::deterministic();
+    let (mut boundary, mut inline, mut outline, mut wrapped) = (0, 0, 0, 0);
+    for _ in 0..512 {
+        let v = any::<OurBytes<RcBytes, 8>>().new_tree(&mut runner).unwrap().current();
+        boundary += (7..=9).contains(&v.len()) as usize;
+        inline += v.is_inline() as usize;
+        outline += (!v.is_inline() && v.len() > 8) as usize;
+        wrapped += (!v.is_inline() && v.len() <= 8) as usize;
+
+        let s = any::<OurString<ArcBytes, 8>>().new_tree(&mut runner).unwrap().current();
+        assert!(std::str::from_utf8(s.as_bytes()).is_ok());
+
+        let _ = any::<OurBytes<RcStr, 8>>().new_tree(&mut runner).unwrap().current();
+        let _ = any::<RcBytes>().new_tree(&mut runner).unwrap().current();
+        let _ = any::<ArcBytes>().new_tree(&mut runner).unwrap().current();
+    }
+    assert!(boundary > 0 && inline > 0 && outline > 0 && wrapped > 0, "{boundary} {inline} {outline} {wrapped}");
+}
+
+#[cfg(feature = "proptest")]
+proptest::proptest! {
+    #[test]
+    fn proptest_roundtrip(a: OurString<RcBytes, 8>, b: OurBytes<Rc<Vec<u8>>, 4>) {
+        assert_eq!(OurString::<RcBytes, 8>::from(a.as_str()), a);
+        assert_eq!(OurBytes::<Rc<Vec<u8>>, 4>::from(b.as_slice()), b);
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn test_arbitrary_large_capacity() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    let mut boundary = 0;
+    for seed in 0..512u32 {
+        let data = (0..1024u32).map(|i| (seed.wrapping_mul(2654435761).wrapping_add(i.wrapping_mul(40503)) >> 7) as u8).collect::<Vec<_>>();
+        let v = OurBytes::<RcBytes, 1000>::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        boundary += (253..=255).contains(&v.len()) as usize;
+    }
+    assert!(boundary > 0);
+}
+
+#[cfg(feature = "proptest")]
+#[test]
+fn test_proptest_large_capacity() {
+    use proptest::strategy::{Strategy, ValueTree};
+    use proptest::test_runner::TestRunner;
+    use proptest::arbitrary::any;
+
+    let mut runner = TestRunner::deterministic();
+    let mut boundary = 0;
+    for _ in 0..512 {
+        let v = any::<OurBytes<RcBytes, 1000>>().new_tree(&mut runner).unwrap().current();
+        boundary += (253..=255).contains(&v.len()) as usize;
+        let s = any::<OurString<RcBytes, 1000>>().new_tree(&mut runner).unwrap().current();
+        boundary += (253..=255).contains(&s.len()) as usize;
+    }
+    assert!(boundary > 0);
+}
@@ -0,0 +1,107 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use our_string::{AtomicOurString, OurString};
+use our_string::comrades::{ArcBytes, RcBytes};
+
+#[test]
+fn test_traits() {
+    macro_rules! assert_impl {
+        ($t:ty : $($tr:tt)*) => {{
+            fn checker<T: $($tr)*>() {}
+            checker::<$t>();
+        }};
+    }
+    macro_rules! assert_not_impl {
+        ($t:ty : $($tr:tt)*) => {
+            const _: fn() -> () = || {
+                struct Check<T: ?Sized>(T);
+                trait Foo<A> { fn foo() {} }
+                impl<T: ?Sized> Foo<()> for Check<T> {}
+                impl<T: ?Sized + $($tr)*> Foo<u8> for Check<T> {}
+                <Check::<$t> as Foo<_>>::foo()
+            };
+        };
+    }
+
+    assert_impl!(AtomicOurString<ArcBytes, 16> : Send + Sync + Default + core::fmt::Debug + From<OurString<ArcBytes, 16>>);
+    assert_not_impl!(AtomicOurString<RcBytes, 16> : Send);
+    assert_not_impl!(AtomicOurString<RcBytes, 16> : Sync);
+}
+
+#[test]
+fn test_basic() {
+    let cell = AtomicOurString::<ArcBytes, 8>::default();
+    assert_eq!(cell.load(), "");
+    cell.store(OurString::from("hello world"));
+    let a = cell.load();
+    let b = cell.load();
+    assert_eq!(a, "hello world");
+    assert_eq!(a.as_ptr(), b.as_ptr());
+
+    let old = cell.swap(OurString::from("short"));
+    assert_eq!(old, "hello world");
+    assert_eq!(old.as_ptr(), a.as_ptr());
+    assert_eq!(cell.load(), "short");
+    assert_eq!(format!("{cell:?}"), "AtomicOurString(\"short\")");
+
+    assert_eq!(cell.compare_and_swap("nope", OurString::from("x")).unwrap_err(), "short");
+    assert_eq!(cell.load(), "short");
+    assert_eq!(cell.compare_and_swap("short", OurString::from("x")).unwrap(), "short");
+    assert_eq!(cell.load(), "x");
+
+    let mut cell = cell;
+    *cell.get_mut() = OurString::from("y");
+    assert_eq!(cell.into_inner(), "y");
+}
+
+#[test]
+fn test_threads() {
+    let (threads, iters) = if cfg!(miri) { (4, 16) } else { (8, 2000) };
+    let values = ["a", "bb", "help me obi-wan kenobi", "you're my only hope"];
+
+    let cell = AtomicOurString::<ArcBytes, 8>::new(OurString::from(values[0]));
+    std::thread::scope(|s| {
+        for t in 0..threads {
+            let cell = &cell;
+            s.spawn(move || {
+                for i in 0..iters {
+                    if t % 2 == 0 {
+                        let v = cell.load();
+                        assert_eq!(values.contains(&v.as_str()), true);
+                    } else if i % 3 == 0 {
+                        let current = cell.load();
+                        let _ = cell.compare_and_swap(&current, OurString::from(values[(i + t) % values.len()]));
+                    } else {
+                        let old = cell.swap(OurString::from(values[i % values.len()]));
+                        assert_eq!(values.contains(&old.as_str()), true);
+                    }
+                }
+            });
+        }
+    });
+    assert_eq!(values.contains(&cell.load().as_str()), true);
+}
+
+#[test]
+fn test_load_panic() {
+    struct Panicky(std::rc::Rc<[u8]>);
+    impl Clone for Panicky {
+        fn clone(&self) -> Self {
+            panic!("clone failed");
+        }
+    }
+    impl our_string::Comrade for Panicky {
+        fn from_slice(s: &[u8]) -> Self {
+            Self(s.into())
+        }
+        fn as_slice(&self) -> &[u8] {
+            &self.0
+        }
+    }
+
+    let cell = AtomicOurString::<Panicky, 0>::new(OurString::from("hello"));
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| cell.load()));
+    assert_eq!(res.is_err(), true);
+    cell.store(OurString::from("world")); // would spin forever if the reader slot was leaked
+    assert_eq!(cell.into_inner(), "world");
+}
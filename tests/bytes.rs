@@ -1,4 +1,6 @@
-use std::cmp::{PartialEq, Eq, PartialOrd, Ord};
+#![allow(clippy::bool_assert_comparison)]
+
+use std::cmp::{Ordering, PartialEq, Eq, PartialOrd, Ord};
 use std::hash::{DefaultHasher, Hash, Hasher};
 use std::borrow::Borrow;
 use std::mem::size_of;
@@ -7,7 +9,8 @@ use std::fmt::Debug;
 use std::sync::Arc;
 use std::rc::Rc;
 
-use our_string::{OurBytes, Comrade};
+use our_string::{OurBytes, Comrade, HeapUsage};
+use our_string::comrades::{RcBytes, ArcBytes, RcBytesW};
 
 fn hash<T: Hash>(t: &T) -> u64 {
     let mut s = DefaultHasher::new();
@@ -372,3 +375,205 @@ fn test_convert() {
     assert_eq!(is_inline(&e), false);
     assert_eq!(d.as_slice().as_ptr(), e.as_slice().as_ptr());
 }
+
+#[test]
+fn test_downgrade() {
+    let a = OurBytes::<Rc<Vec<u8>>, 4>::from([1u8, 2, 3].as_slice());
+    let w = a.downgrade();
+    drop(a);
+    assert_eq!(w.upgrade().unwrap(), [1u8, 2, 3].as_slice());
+
+    let a = OurBytes::<Rc<Vec<u8>>, 4>::from([1u8, 2, 3, 4, 5].as_slice());
+    let w = a.downgrade();
+    assert_eq!(w.upgrade().unwrap().as_ptr(), a.as_ptr());
+    drop(a);
+    assert_eq!(w.upgrade().is_none(), true);
+}
+
+#[test]
+fn test_convert_comrade() {
+    let a = OurBytes::<Rc<[u8]>, 8>::from([5u8, 1, 6].as_slice());
+    let b: OurBytes<Arc<[u8]>, 4> = a.convert_comrade();
+    assert_eq!(b.is_inline(), true);
+    assert_eq!(b, [5u8, 1, 6].as_slice());
+    let c: OurBytes<Arc<[u8]>, 2> = b.convert_comrade();
+    assert_eq!(c.is_inline(), false);
+    assert_eq!(c, [5u8, 1, 6].as_slice());
+
+    let a = OurBytes::<RcBytes, 4>::from([1u8, 2, 3, 4, 5].as_slice());
+    let ptr = a.as_ptr();
+    let b: OurBytes<ArcBytes, 4> = a.convert_comrade();
+    assert_eq!(b.as_ptr(), ptr);
+    let c: OurBytes<Arc<[u8]>, 4> = b.clone().convert_comrade();
+    assert_ne!(c.as_ptr(), ptr);
+    assert_eq!(c, b);
+    let d: OurBytes<ArcBytes, 8> = b.convert_comrade();
+    assert_eq!(d.is_inline(), false);
+    assert_eq!(d.as_ptr(), ptr);
+}
+
+#[test]
+fn test_convert_compact() {
+    let a = OurBytes::<Rc<[u8]>, 4>::from([1u8, 2, 3, 4, 5, 6].as_slice());
+    assert_eq!(a.is_inline(), false);
+    let b: OurBytes<Rc<[u8]>, 8> = a.clone().convert_compact();
+    assert_eq!(b.is_inline(), true);
+    assert_eq!(b, a);
+    let c: OurBytes<Rc<[u8]>, 5> = a.clone().convert_compact();
+    assert_eq!(c.is_inline(), false);
+    assert_eq!(c.as_ptr(), a.as_ptr());
+
+    let d = OurBytes::<Rc<[u8]>, 8>::from([1u8, 2, 3].as_slice());
+    let e: OurBytes<Rc<[u8]>, 2> = d.convert_compact();
+    assert_eq!(e.is_inline(), false);
+    assert_eq!(e, [1u8, 2, 3].as_slice());
+}
+
+#[test]
+fn test_try_convert() {
+    let a = OurBytes::<Rc<[u8]>, 8>::from([1u8, 2, 3].as_slice());
+    let b = a.try_convert::<2>().unwrap_err();
+    assert_eq!(b.is_inline(), true);
+    assert_eq!(b, [1u8, 2, 3].as_slice());
+    let c = b.try_convert::<3>().unwrap();
+    assert_eq!(c.is_inline(), true);
+    assert_eq!(c, [1u8, 2, 3].as_slice());
+
+    let d = OurBytes::<Rc<[u8]>, 2>::from([1u8, 2, 3].as_slice());
+    let e = d.clone().try_convert::<1>().unwrap();
+    assert_eq!(e.is_inline(), false);
+    assert_eq!(e.as_ptr(), d.as_ptr());
+}
+
+#[test]
+fn test_canonical() {
+    for value in [[].as_slice(), &[1u8], &[1, 2, 3, 4], &[1, 2, 3, 4, 5]] {
+        let a = OurBytes::<Rc<Vec<u8>>, 4>::from_comrade_inlining(Rc::new(value.to_vec()));
+        assert_eq!(a, value);
+        assert_eq!(a.is_inline(), value.len() <= 4);
+        assert_eq!(a.is_canonical(), true);
+        a.debug_assert_canonical();
+
+        let mut b = OurBytes::<Rc<Vec<u8>>, 4>::from(Rc::new(value.to_vec()));
+        assert_eq!(b.is_canonical(), value.len() > 4);
+        let ptr = b.as_ptr();
+        b.normalize();
+        assert_eq!(b, value);
+        assert_eq!(b.is_canonical(), true);
+        assert_eq!(b.is_inline(), value.len() <= 4);
+        assert_eq!(b.as_ptr() == ptr, value.len() > 4);
+
+        assert_eq!(OurBytes::<Rc<Vec<u8>>, 4>::from(value).is_canonical(), true);
+    }
+
+    let shared = Rc::new(vec![1u8, 2]);
+    let a = OurBytes::<Rc<Vec<u8>>, 4>::from_comrade_inlining(shared.clone());
+    assert_eq!(Rc::strong_count(&shared), 1);
+    assert_eq!(a, [1u8, 2].as_slice());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "value is not in canonical form")]
+fn test_debug_assert_canonical() {
+    OurBytes::<Rc<Vec<u8>>, 4>::from(Rc::new(vec![1u8])).debug_assert_canonical();
+}
+
+#[test]
+fn test_ptr_eq() {
+    let a = OurBytes::<Rc<Vec<u8>>, 4>::from([1u8, 2, 3, 4, 5].as_slice());
+    let b = a.clone();
+    let c = OurBytes::<Rc<Vec<u8>>, 4>::from([1u8, 2, 3, 4, 5].as_slice());
+    assert_eq!((a.ptr_eq(&b), a.ptr_eq(&c), a == b, a == c, a.cmp(&b), a.cmp(&c)), (true, false, true, true, Ordering::Equal, Ordering::Equal));
+    assert_eq!((a.as_ptr() == b.as_ptr(), a == b, b.as_slice() == a, a.partial_cmp(&b), PartialOrd::partial_cmp(&b.as_slice(), &a)), (true, true, true, Some(Ordering::Equal), Some(Ordering::Equal)));
+
+    let d = OurBytes::<Rc<Vec<u8>>, 4>::from([1u8, 2].as_slice());
+    assert_eq!((d.ptr_eq(&d), d.ptr_eq(&d.clone()), d == d.clone()), (false, false, true));
+
+    // distinct empty vecs share a dangling pointer, but are not the same handle
+    let (e, f) = (Rc::new(Vec::<u8>::new()), Rc::new(Vec::<u8>::new()));
+    assert_eq!((e.as_slice().as_ptr() == f.as_slice().as_ptr(), Comrade::ptr_eq(&e, &f), Comrade::ptr_eq(&e, &e.clone())), (true, false, true));
+    let (e, f) = (OurBytes::<_, 4>::from(e), OurBytes::<_, 4>::from(f));
+    assert_eq!((e.ptr_eq(&f), e.ptr_eq(&e.clone()), e == f), (false, true, true));
+
+    let g = OurBytes::<RcBytes, 4>::from([1u8, 2, 3, 4, 5].as_slice());
+    assert_eq!((g.ptr_eq(&g.clone()), g.ptr_eq(&OurBytes::from(g.as_slice()))), (true, false));
+}
+
+#[test]
+fn test_introspection() {
+    for value in [[].as_slice(), &[1u8], &[1, 2, 3, 4], &[1, 2, 3, 4, 5]] {
+        let a = OurBytes::<RcBytes, 4>::from(value);
+        assert_eq!(a.is_inline(), is_inline(&a));
+        assert_eq!(a.is_inline(), value.len() <= 4);
+        assert_eq!(a.inline_capacity(), 4);
+        assert_eq!(a.strong_count(), if a.is_inline() { None } else { Some(1) });
+        assert_eq!(a.heap_size(), if a.is_inline() { 0 } else { 2 * size_of::<usize>() + value.len() });
+
+        let b = a.clone();
+        assert_eq!(b.strong_count(), if a.is_inline() { None } else { Some(2) });
+    }
+    assert_eq!(OurBytes::<RcBytes, 0>::new().inline_capacity(), 0);
+    assert_eq!(OurBytes::<RcBytes, 254>::new().inline_capacity(), 254);
+    assert_eq!(OurBytes::<RcBytes, 1000>::new().inline_capacity(), 254);
+
+    let c = OurBytes::<Rc<[u8]>, 4>::from(Rc::<[u8]>::from([1u8, 2].as_slice()));
+    assert_eq!((c.is_inline(), c.strong_count(), c.heap_size()), (false, Some(1), 2 * size_of::<usize>() + 8));
+}
+
+#[test]
+fn test_heap_usage() {
+    let a = OurBytes::<RcBytes, 4>::from([1u8, 2, 3, 4, 5].as_slice());
+    let b = OurBytes::<RcBytes, 4>::from([1u8, 2, 3, 4, 5, 6].as_slice());
+    let c = OurBytes::<RcBytes, 4>::from([1u8, 2].as_slice());
+    let values = [a.clone(), a.clone(), b.clone(), c.clone(), a.clone()];
+
+    let mut usage = HeapUsage::new();
+    usage.extend(values.iter());
+    assert_eq!((usage.total(), usage.allocations()), (a.heap_size() + b.heap_size(), 2));
+    assert_eq!(values.iter().map(|x| x.heap_size()).sum::<usize>(), 3 * a.heap_size() + b.heap_size());
+
+    assert_eq!(usage.add_slice(&a), 0);
+    let d = OurBytes::<RcBytes, 4>::from([1u8, 2, 3, 4, 5].as_slice());
+    assert_eq!(usage.add_slice(&d), d.heap_size());
+    assert_eq!((usage.total(), usage.allocations()), (2 * a.heap_size() + b.heap_size(), 3));
+
+    // distinct empty vecs share a dangling pointer, but are separate allocations
+    let e = OurBytes::<Rc<Vec<u8>>, 4>::from(Rc::new(Vec::new()));
+    let f = OurBytes::<Rc<Vec<u8>>, 4>::from(Rc::new(Vec::new()));
+    let mut usage = HeapUsage::new();
+    usage.extend([&e, &f, &e.clone()]);
+    assert_eq!((usage.total(), usage.allocations()), (e.heap_size() + f.heap_size(), 2));
+}
+
+#[test]
+fn test_into_comrade() {
+    let shared = Arc::new(vec![1u8, 2, 3, 4, 5]);
+    let a = OurBytes::<Arc<Vec<u8>>, 4>::from(shared.clone());
+    assert_eq!(a.as_comrade().map(|x| Arc::ptr_eq(x, &shared)), Some(true));
+    let b = a.clone().into_comrade().unwrap();
+    assert_eq!(Arc::ptr_eq(&b, &shared), true);
+    assert_eq!(Arc::ptr_eq(&a.into_comrade_or_alloc(), &shared), true);
+    assert_eq!(Arc::strong_count(&shared), 2);
+
+    let c = OurBytes::<Arc<Vec<u8>>, 4>::from([1u8, 2].as_slice());
+    assert_eq!(c.as_comrade().is_none(), true);
+    let c = c.into_comrade().unwrap_err();
+    assert_eq!(c, [1u8, 2].as_slice());
+    let d = c.into_comrade_or_alloc();
+    assert_eq!(*d, vec![1u8, 2]);
+    assert_eq!(Arc::strong_count(&d), 1);
+}
+
+#[test]
+fn test_clone_large_inline() {
+    for len in [0, 1, 7, 8, 9, 127, 128, 253, 254] {
+        let value = (0..len).map(|i| i as u8).collect::<Vec<_>>();
+        let a = OurBytes::<RcBytesW, 254>::from(value.as_slice());
+        let b = a.clone();
+        assert_eq!((b.is_inline(), b.as_slice()), (true, value.as_slice()));
+        drop(a);
+        assert_eq!(b.downgrade().upgrade().unwrap(), value.as_slice());
+        assert_eq!(b.clone().convert::<128>(), value.as_slice());
+    }
+}
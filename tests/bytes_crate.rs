@@ -0,0 +1,94 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg(feature = "bytes")]
+
+use bytes::{Buf, Bytes};
+
+use our_string::{OurString, OurBytes, OurBuf};
+use our_string::comrades::ArcBytes;
+
+#[test]
+fn test_comrade() {
+    let frame = Bytes::from_static(b"a received network frame");
+    let a = OurBytes::<Bytes, 8>::from(frame.clone());
+    assert_eq!(a, b"a received network frame".as_slice());
+    assert_eq!(a.as_ptr(), frame.as_ptr());
+
+    let b = OurBytes::<Bytes, 8>::from(b"another frame".as_slice());
+    assert_eq!(b, b"another frame".as_slice());
+    let c = OurString::<Bytes, 8>::from("hi");
+    assert_eq!(c, "hi");
+}
+
+#[test]
+fn test_into_bytes() {
+    let a = OurBytes::<ArcBytes, 8>::from(b"hello world, again".as_slice());
+    let b = Bytes::from(a.clone());
+    assert_eq!(b, b"hello world, again".as_slice());
+    assert_eq!(b.as_ptr(), a.as_ptr());
+    drop(a);
+    assert_eq!(b.slice(6..11), b"world".as_slice());
+
+    let a = OurBytes::<ArcBytes, 8>::from(b"hello".as_slice());
+    assert_eq!(Bytes::from(a), b"hello".as_slice());
+
+    let a = OurString::<ArcBytes, 8>::from("hello world, again");
+    let p = a.as_ptr();
+    let b = Bytes::from(a);
+    assert_eq!(b.as_ptr(), p);
+    std::thread::spawn(move || assert_eq!(b, b"hello world, again".as_slice())).join().unwrap();
+}
+
+#[test]
+fn test_buf() {
+    for value in ["hi", "hello world, again"] {
+        let mut buf = OurBuf::from(OurString::<ArcBytes, 8>::from(value));
+        assert_eq!(buf.remaining(), value.len());
+        assert_eq!(buf.chunk(), value.as_bytes());
+        assert_eq!(buf.get_u8(), value.as_bytes()[0]);
+        assert_eq!(buf.remaining(), value.len() - 1);
+        assert_eq!(buf.chunk(), &value.as_bytes()[1..]);
+        buf.advance(1);
+        assert_eq!(buf.copy_to_bytes(buf.remaining()), &value.as_bytes()[2..]);
+        assert_eq!(buf.has_remaining(), false);
+        assert_eq!(buf.into_inner(), value.as_bytes());
+    }
+
+    let mut buf = OurBuf::from(OurBytes::<ArcBytes, 8>::from([0u8, 0, 1, 2, 3, 4, 5, 6, 7].as_slice()));
+    assert_eq!(buf.get_u16(), 0);
+    assert_eq!(buf.get_u32_le(), 0x04030201);
+}
+
+#[test]
+fn test_buf_copy_to_bytes() {
+    let a = OurBytes::<ArcBytes, 4>::from(b"hello world".as_slice());
+    let mut buf = OurBuf::from(a.clone());
+    buf.advance(2);
+    let b = buf.copy_to_bytes(5);
+    assert_eq!((b.as_ref(), b.as_ptr()), (b"llo w".as_slice(), a[2..].as_ptr()));
+    assert_eq!((buf.remaining(), buf.chunk()), (4, b"orld".as_slice()));
+
+    let frame = Bytes::from(b"a received network frame".to_vec());
+    let mut buf = OurBuf::from(OurBytes::<Bytes, 4>::from(frame.clone()));
+    let c = buf.copy_to_bytes(10);
+    assert_eq!((c.as_ref(), c.as_ptr()), (b"a received".as_slice(), frame.as_ptr()));
+
+    let mut buf = OurBuf::from(OurBytes::<std::rc::Rc<[u8]>, 4>::from(b"hello world".as_slice()));
+    buf.advance(6);
+    assert_eq!(buf.copy_to_bytes(5), b"world".as_slice());
+    let mut buf = OurBuf::from(OurBytes::<ArcBytes, 8>::from(b"hi".as_slice()));
+    assert_eq!(buf.copy_to_bytes(2), b"hi".as_slice());
+}
+
+#[test]
+#[should_panic]
+fn test_buf_copy_to_bytes_past_end() {
+    let mut buf = OurBuf::from(OurBytes::<ArcBytes, 8>::from(b"hello world".as_slice()));
+    buf.copy_to_bytes(12);
+}
+
+#[test]
+#[should_panic]
+fn test_buf_advance_past_end() {
+    let mut buf = OurBuf::from(OurBytes::<ArcBytes, 8>::from(b"hi".as_slice()));
+    buf.advance(3);
+}
@@ -1,4 +1,7 @@
-use our_string::comrades::{RcBytes, ArcBytes};
+#![allow(clippy::bool_assert_comparison)]
+
+use our_string::{Comrade, ComradeFrom};
+use our_string::comrades::{RcBytes, ArcBytes, RcBytesW, ArcBytesW, WeakRcBytes, WeakArcBytes, RcSliceW, RcBytes32, ArcBytes32, RcBytesAligned, ArcBytesAligned, RcSlice, RcStr};
 
 #[test]
 fn test_traits() {
@@ -28,6 +31,19 @@ fn test_traits() {
     assert_impl!(RcBytes : core::fmt::Debug + Default + Clone + PartialEq + Eq + PartialOrd + Ord + core::ops::Deref<Target = [u8]> + AsRef<[u8]> + core::borrow::Borrow<[u8]> + core::hash::Hash);
     assert_not_impl!(RcBytes : Send);
     assert_not_impl!(RcBytes : Sync);
+
+    assert_impl!(ArcBytes32 : Send + Sync + core::fmt::Debug + Default + Clone + PartialEq + Eq + PartialOrd + Ord + core::ops::Deref<Target = [u8]> + AsRef<[u8]> + core::borrow::Borrow<[u8]> + core::hash::Hash);
+    assert_impl!(RcBytes32 : core::fmt::Debug + Default + Clone + PartialEq + Eq + PartialOrd + Ord + core::ops::Deref<Target = [u8]> + AsRef<[u8]> + core::borrow::Borrow<[u8]> + core::hash::Hash);
+    assert_not_impl!(RcBytes32 : Send);
+    assert_not_impl!(RcBytes32 : Sync);
+
+    assert_impl!(ArcBytesAligned<64> : Send + Sync + core::fmt::Debug + Default + Clone + Eq + Ord + core::ops::Deref<Target = [u8]> + core::hash::Hash + our_string::ThinComrade);
+    assert_not_impl!(RcBytesAligned<64> : Send);
+
+    assert_impl!(WeakArcBytes : Send + Sync + core::fmt::Debug + Clone);
+    assert_impl!(WeakRcBytes : core::fmt::Debug + Clone);
+    assert_not_impl!(WeakRcBytes : Send);
+    assert_not_impl!(WeakRcBytes : Sync);
 }
 
 #[test]
@@ -83,3 +99,185 @@ fn test_arc_bytes() {
     assert_eq!(empty.is_empty(), true);
     assert_eq!(empty, &[] as &[u8]);
 }
+
+#[test]
+fn test_arc_bytes_threads() {
+    let (threads, clones) = if cfg!(miri) { (4, 16) } else { (16, 1024) };
+
+    let value = ArcBytes::from(b"help me obi-wan kenobi, you're my only hope".as_slice());
+    let handles = (0..threads).map(|_| {
+        let value = value.clone();
+        std::thread::spawn(move || {
+            let copies = (0..clones).map(|_| value.clone()).collect::<Vec<_>>();
+            for copy in copies.iter() {
+                assert_eq!(copy.as_ptr(), value.as_ptr());
+                assert_eq!(copy, b"help me obi-wan kenobi, you're my only hope");
+            }
+        })
+    }).collect::<Vec<_>>();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(value, b"help me obi-wan kenobi, you're my only hope");
+}
+
+#[test]
+fn test_rc_bytes_many_clones() {
+    let value = RcBytes::from(b"hello world".as_slice());
+    let copies = (0..1024).map(|_| value.clone()).collect::<Vec<_>>();
+    drop(value);
+    for copy in copies.iter() {
+        assert_eq!(copy.as_ptr(), copies[0].as_ptr());
+        assert_eq!(copy, b"hello world");
+    }
+}
+
+#[test]
+fn test_weak() {
+    let a = RcBytesW::from(b"hello world".as_slice());
+    let w = RcBytesW::downgrade(&a);
+    let ww = w.clone();
+    assert_eq!(w.upgrade().unwrap().as_ptr(), a.as_ptr());
+    assert_eq!(ww.upgrade().unwrap(), b"hello world");
+    drop(a);
+    assert_eq!(w.upgrade().is_none(), true);
+    assert_eq!(ww.upgrade().is_none(), true);
+
+    let a = ArcBytesW::from(b"hello world".as_slice());
+    let w = ArcBytesW::downgrade(&a);
+    drop(w);
+    let w = ArcBytesW::downgrade(&a);
+    let b = w.upgrade().unwrap();
+    drop(a);
+    assert_eq!(b, b"hello world");
+    let w = std::thread::spawn(move || {
+        assert_eq!(w.upgrade().unwrap(), b"hello world");
+        w
+    }).join().unwrap();
+    drop(b);
+    assert_eq!(w.upgrade().is_none(), true);
+}
+
+#[test]
+fn test_comrade_from() {
+    let a = RcBytes::from(b"hello world".as_slice());
+    let ptr = a.as_ptr();
+    let b = ArcBytes::comrade_from(a);
+    assert_eq!(b.as_ptr(), ptr);
+    assert_eq!(b, b"hello world");
+    let c = RcBytes::comrade_from(b);
+    assert_eq!(c.as_ptr(), ptr);
+    assert_eq!(c, b"hello world");
+
+    let cc = c.clone();
+    let d = ArcBytes::comrade_from(c);
+    assert_ne!(d.as_ptr(), ptr);
+    assert_eq!(d, b"hello world");
+    assert_eq!(cc, b"hello world");
+
+    let e = ArcBytes::comrade_from(cc);
+    assert_eq!(e.as_ptr(), ptr);
+    assert_eq!(e, b"hello world");
+
+    let a = RcBytesW::from(b"hello world".as_slice());
+    let ptr = a.as_ptr();
+    let b = ArcBytesW::comrade_from(a);
+    assert_eq!(b.as_ptr(), ptr);
+    let c = RcBytesW::comrade_from(b);
+    assert_eq!((c.as_ptr(), &*c), (ptr, b"hello world".as_slice()));
+    let w = RcBytesW::downgrade(&c);
+    let d = ArcBytesW::comrade_from(c);
+    assert_ne!(d.as_ptr(), ptr);
+    assert_eq!(d, b"hello world");
+    assert_eq!(w.upgrade().is_none(), true);
+
+    let f = ArcBytes::comrade_from(std::rc::Rc::<[u8]>::from(b"hello".as_slice()));
+    assert_eq!(f, b"hello");
+    let g = std::sync::Arc::<Vec<u8>>::comrade_from(f);
+    assert_eq!(g.as_slice(), b"hello");
+}
+
+#[test]
+fn test_compact_bytes() {
+    assert_eq!(size_of::<RcBytes32>(), size_of::<usize>());
+    assert_eq!(size_of::<Option<ArcBytes32>>(), size_of::<usize>());
+
+    for value in ["".as_bytes(), b"h", b"help", b"help me obi-wan kenobi, you're my only hope"] {
+        let v = RcBytes32::from(value);
+        assert_eq!(v, value);
+        assert_eq!(v.as_ptr() as usize % align_of::<u32>(), 0);
+        let vv = v.clone();
+        assert_eq!(v.as_ptr(), vv.as_ptr());
+        drop(v);
+        assert_eq!(&*vv, value);
+
+        let v = ArcBytes32::try_from_slice(value).unwrap();
+        let vv = v.clone();
+        drop(v);
+        std::thread::spawn(move || assert_eq!(&*vv, value)).join().unwrap();
+    }
+    assert_eq!(RcBytes32::default().is_empty(), true);
+}
+
+#[test]
+fn test_aligned_bytes() {
+    for len in [0, 1, 7, 8, 63, 64, 65, 1000] {
+        let value = (0..len).map(|i| i as u8).collect::<Vec<_>>();
+
+        let a = RcBytesAligned::<64>::from(value.as_slice());
+        assert_eq!(&*a, value.as_slice());
+        assert_eq!(a.as_ptr() as usize % 64, 0);
+        let (prefix, _, _) = unsafe { a.align_to::<u64>() };
+        assert_eq!(prefix.is_empty(), true);
+
+        let b = ArcBytesAligned::<32>::from(value.as_slice());
+        let bb = b.clone();
+        assert_eq!(bb.as_ptr(), b.as_ptr());
+        assert_eq!(b.as_ptr() as usize % 32, 0);
+        drop(b);
+        std::thread::spawn(move || assert_eq!(&*bb, value.as_slice())).join().unwrap();
+
+        let c = RcSlice::<u16, 16>::from(vec![7u16; len].as_slice());
+        assert_eq!(c.as_ptr() as usize % 16, 0);
+        assert_eq!(c.len(), len);
+    }
+
+    let a = our_string::OurBytes::<RcBytesAligned<64>, 8>::from([3u8; 100].as_slice());
+    assert_eq!(a.as_ptr() as usize % 64, 0);
+
+    let w = RcSliceW::<u8, 128>::downgrade(&RcSliceW::<u8, 128>::from(b"hello".as_slice()));
+    assert_eq!(w.upgrade().is_none(), true);
+}
+
+#[test]
+fn test_counts_and_sizes() {
+    const W: usize = size_of::<usize>();
+
+    let a = RcBytes::from(b"hello".as_slice());
+    let b = a.clone();
+    assert_eq!((RcBytes::strong_count(&a), a.strong_count(), a.heap_size()), (2, Some(2), Some(2 * W + 5)));
+    drop(b);
+
+    let a = RcBytesW::from(b"hello".as_slice());
+    let b = a.clone();
+    let weak = RcBytesW::downgrade(&a);
+    assert_eq!((RcBytesW::strong_count(&a), a.strong_count(), a.heap_size()), (2, Some(2), Some(3 * W + 5)));
+    drop(b);
+    assert_eq!((ArcBytesW::strong_count(&ArcBytesW::from(b"hi".as_slice())), a.strong_count()), (1, Some(1)));
+    drop(weak);
+
+    let c = RcBytes32::from(b"hello".as_slice());
+    let d = c.clone();
+    assert_eq!((RcBytes32::strong_count(&c), Comrade::strong_count(&d), c.heap_size()), (2, Some(2), Some(8 + 5)));
+    assert_eq!(ArcBytes32::from(b"hi".as_slice()).heap_size(), Some(8 + 2));
+
+    assert_eq!(RcBytesAligned::<64>::from(b"hello".as_slice()).heap_size(), Some(64 + 5));
+    assert_eq!(RcSlice::<u32>::from([1u32, 2, 3].as_slice()).heap_size(), Some(2 * W + 12));
+
+    let e = std::rc::Rc::<[u8]>::from(b"hello".as_slice());
+    assert_eq!((Comrade::strong_count(&e), Comrade::heap_size(&e)), (Some(1), Some(2 * W + 8)));
+    let f = std::sync::Arc::new(b"hello".to_vec());
+    assert_eq!((Comrade::strong_count(&f), Comrade::heap_size(&f)), (Some(1), Some(2 * W + size_of::<Vec<u8>>() + 5)));
+    let g = RcStr::from(std::rc::Rc::<str>::from("hello"));
+    assert_eq!((RcStr::strong_count(&g), Comrade::strong_count(&g), Comrade::heap_size(&g)), (1, Some(1), Some(2 * W + 8)));
+}
@@ -0,0 +1,19 @@
+#![cfg(feature = "testing")]
+
+use std::sync::Arc;
+use std::rc::Rc;
+
+use our_string::comrade_conformance;
+use our_string::comrades::{RcBytes, ArcBytes, RcBytes32, ArcBytes32, RcBytesAligned, RcStr, ArcStr};
+
+comrade_conformance!(rc_slice: Rc<[u8]>);
+comrade_conformance!(rc_vec: Rc<Vec<u8>>);
+comrade_conformance!(arc_slice: Arc<[u8]>);
+comrade_conformance!(arc_vec: Arc<Vec<u8>>);
+comrade_conformance!(rc_str: RcStr);
+comrade_conformance!(arc_str: ArcStr);
+comrade_conformance!(rc_bytes: RcBytes);
+comrade_conformance!(arc_bytes: ArcBytes);
+comrade_conformance!(rc_bytes_32: RcBytes32);
+comrade_conformance!(arc_bytes_32: ArcBytes32);
+comrade_conformance!(rc_bytes_aligned: RcBytesAligned<64>);
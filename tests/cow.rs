@@ -0,0 +1,76 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::rc::Rc;
+
+use our_string::{OurCow, OurString};
+use our_string::comrades::RcBytes;
+
+fn hash<T: Hash + ?Sized>(v: &T) -> u64 {
+    let mut h = DefaultHasher::new();
+    v.hash(&mut h);
+    h.finish()
+}
+
+#[test]
+fn test_traits() {
+    macro_rules! assert_impl {
+        ($t:ty : $($tr:tt)*) => {{
+            fn checker<T: $($tr)*>() {}
+            checker::<$t>();
+        }};
+    }
+
+    assert_impl!(OurCow<'static, RcBytes, 8> : Clone + Default + std::fmt::Debug + std::fmt::Display + Eq + Ord + Hash + std::ops::Deref<Target = str> + AsRef<str> + std::borrow::Borrow<str> + From<&'static str> + From<OurString<RcBytes, 8>>);
+    assert_impl!(OurString<RcBytes, 8> : From<OurCow<'static, RcBytes, 8>>);
+}
+
+#[test]
+fn test_into_owned() {
+    let buffer = String::from("short and a much longer field");
+    let a = OurCow::<RcBytes, 8>::from(&buffer[..5]);
+    let b = OurCow::<RcBytes, 8>::from(&buffer[10..]);
+    assert_eq!(a.is_borrowed(), true);
+    assert_eq!(a.as_ptr(), buffer.as_ptr());
+    assert_eq!(b, "a much longer field");
+
+    let a = a.into_owned();
+    let b = b.into_owned();
+    drop(buffer);
+    assert_eq!(a, "short");
+    assert_eq!(b, "a much longer field");
+
+    let c = OurCow::<RcBytes, 8>::from(b.clone());
+    assert_eq!(c.is_owned(), true);
+    assert_eq!(c.clone().into_owned().as_ptr(), b.as_ptr());
+    assert_eq!(OurCow::<Rc<[u8]>, 8>::default(), "");
+}
+
+#[test]
+fn test_semantics() {
+    let owned = OurString::<RcBytes, 4>::from("hello world");
+    let borrowed = OurCow::<RcBytes, 4>::from("hello world");
+    let shared = OurCow::<RcBytes, 4>::from(owned.clone());
+
+    assert_eq!(borrowed, shared);
+    assert_eq!(owned, borrowed);
+    assert_eq!("hello world", borrowed);
+    assert_eq!(hash(&borrowed), hash(&shared));
+    assert_eq!(hash(&borrowed), hash(&owned));
+    assert_eq!(hash(&borrowed), hash("hello world"));
+    assert_eq!("abc" < borrowed, true);
+    assert_eq!(borrowed.cmp(&OurCow::from("abc")), std::cmp::Ordering::Greater);
+    assert_eq!(format!("{borrowed:?} {shared}"), "\"hello world\" hello world");
+
+    let mut map = HashMap::new();
+    map.insert(borrowed, 1);
+    assert_eq!(map.insert(shared, 2), Some(1));
+    assert_eq!(map["hello world"], 2);
+
+    let mut map = BTreeMap::new();
+    map.insert(OurCow::<RcBytes, 4>::from("b"), 1);
+    map.insert(OurCow::from(OurString::from("a")), 2);
+    assert_eq!(map.keys().map(|x| x.as_str()).collect::<Vec<_>>(), ["a", "b"]);
+    assert_eq!(map["b"], 1);
+}
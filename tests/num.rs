@@ -0,0 +1,87 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use our_string::OurString;
+use our_string::comrades::RcBytes;
+
+#[test]
+fn test_ints() {
+    macro_rules! check {
+        ($($t:ty),*) => {$(
+            for value in [<$t>::MIN, <$t>::MIN / 3, <$t>::MAX, <$t>::MAX / 7, 0, 1, 9, 10, 99, 100, 127] {
+                let a = OurString::<RcBytes, 15>::from(value);
+                assert_eq!(a, value.to_string());
+                assert_eq!(a.is_inline(), a.len() <= 15);
+            }
+        )*};
+    }
+    check!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+    for value in [-1i32, -9, -10, -100] {
+        assert_eq!(OurString::<RcBytes, 15>::from(value), value.to_string());
+    }
+    let a = OurString::<RcBytes, 7>::from(u128::MAX);
+    assert_eq!(a.is_inline(), false);
+    assert_eq!(a, "340282366920938463463374607431768211455");
+}
+
+#[test]
+fn test_floats() {
+    for value in [0.0, -0.0, 1.0, -1.5, 0.1, 1.0 / 3.0, 1e21, 1e-7, f64::MAX, f64::MIN, f64::MIN_POSITIVE, -f64::from_bits(1), f64::EPSILON, f64::INFINITY, f64::NEG_INFINITY, f64::NAN] {
+        let a = OurString::<RcBytes, 15>::from_f64(value);
+        assert_eq!(a, value.to_string());
+        assert_eq!(a.is_inline(), a.len() <= 15);
+    }
+    assert_eq!(OurString::<RcBytes, 15>::from_f64(-f64::from_bits(1)).len(), 327);
+}
+
+#[test]
+fn test_inline_boundary() {
+    let a = OurString::<RcBytes, 3>::from(-99i8);
+    let b = OurString::<RcBytes, 3>::from(-100i8);
+    let c = OurString::<RcBytes, 3>::from(999u16);
+    let d = OurString::<RcBytes, 3>::from(1000u16);
+    assert_eq!((a.as_str(), a.is_inline()), ("-99", true));
+    assert_eq!((b.as_str(), b.is_inline()), ("-100", false));
+    assert_eq!((c.as_str(), c.is_inline()), ("999", true));
+    assert_eq!((d.as_str(), d.is_inline()), ("1000", false));
+
+    let e = OurString::<RcBytes, 0>::from(0u8);
+    let f = OurString::<RcBytes, 1000>::from(i128::MIN);
+    assert_eq!((e.as_str(), e.is_inline()), ("0", false));
+    assert_eq!((f.as_str(), f.is_inline()), ("-170141183460469231731687303715884105728", true));
+
+    let g = OurString::<RcBytes, 4>::from_f64(-1.5);
+    let h = OurString::<RcBytes, 4>::from_f64(-1.25);
+    let i = OurString::<RcBytes, 1000>::from_f64(f64::MAX);
+    let j = OurString::<RcBytes, 1000>::from_f64(-f64::from_bits(1));
+    assert_eq!((g.as_str(), g.is_inline()), ("-1.5", true));
+    assert_eq!((h.as_str(), h.is_inline()), ("-1.25", false));
+    assert_eq!((i.len(), i.is_inline()), (309, false));
+    assert_eq!((j.len(), j.is_inline()), (327, false));
+    let k = OurString::<RcBytes, 254>::from_f64(f64::MIN_POSITIVE * 1e200);
+    assert_eq!((k.as_str(), k.is_inline()), ((f64::MIN_POSITIVE * 1e200).to_string().as_str(), true));
+}
+
+#[test]
+fn test_bools() {
+    let a = OurString::<RcBytes, 4>::from_bool(true);
+    let b = OurString::<RcBytes, 4>::from_bool(false);
+    assert_eq!((a.as_str(), a.is_inline()), ("true", true));
+    assert_eq!((b.as_str(), b.is_inline()), ("false", false));
+}
+
+#[cfg(not(miri))]
+proptest::proptest! {
+    #[test]
+    fn proptest_ints(a: i128, b: u64, c: i32) {
+        assert_eq!(OurString::<RcBytes, 15>::from(a), a.to_string());
+        assert_eq!(OurString::<RcBytes, 15>::from(b), b.to_string());
+        assert_eq!(OurString::<RcBytes, 15>::from(c), c.to_string());
+    }
+
+    #[test]
+    fn proptest_floats(bits: u64) {
+        let value = f64::from_bits(bits);
+        assert_eq!(OurString::<RcBytes, 15>::from_f64(value), value.to_string());
+    }
+}
@@ -0,0 +1,97 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use std::cmp::{PartialEq, Eq, PartialOrd, Ord};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::fmt::{Debug, Display};
+use std::mem::size_of;
+use std::rc::Rc;
+
+use our_string::{PackedOurString, OurString};
+use our_string::comrades::{RcBytes, ArcBytes};
+
+fn hash<T: Hash + ?Sized>(t: &T) -> u64 {
+    let mut s = DefaultHasher::new();
+    t.hash(&mut s);
+    s.finish()
+}
+
+#[test]
+fn test_sizes() {
+    assert_eq!(size_of::<PackedOurString<RcBytes, 15>>(), size_of::<OurString<RcBytes, 15>>());
+    assert_eq!(size_of::<PackedOurString<RcBytes, 15>>(), 16);
+    assert_eq!(size_of::<Option<PackedOurString<RcBytes, 14>>>(), 16);
+    assert_eq!(size_of::<PackedOurString<Rc<[u8]>, 23>>(), size_of::<String>());
+}
+
+#[test]
+fn test_traits() {
+    macro_rules! assert_impl {
+        ($t:ty : $($tr:tt)*) => {{
+            fn checker<T: $($tr)*>() {}
+            checker::<$t>();
+        }};
+    }
+
+    assert_impl!(PackedOurString<RcBytes, 15> : Hash + Clone + Debug + Display + PartialEq + PartialEq<str> + for<'a> PartialEq<&'a str> + Eq + PartialOrd + Ord + Default + for<'a> From<&'a str>);
+    assert_impl!(PackedOurString<ArcBytes, 15> : Send + Sync + Hash + Clone + Debug + Display + PartialEq + Eq + PartialOrd + Ord + Default + for<'a> From<&'a str>);
+}
+
+#[test]
+fn test_packing() {
+    let source = "abcdefghijklmnopqrstuvwxyz0123456789";
+    for len in 0..source.len() {
+        let v = PackedOurString::<RcBytes, 15>::from(&source[..len]);
+        assert_eq!(v, &source[..len]);
+        assert_eq!(v.len(), len);
+        assert_eq!(v.is_empty(), len == 0);
+        assert_eq!(v.is_packed(), len > 15 && len <= 17);
+        assert_eq!(v.as_str().is_some(), !v.is_packed());
+        assert_eq!(v.with_str(|s| s.to_owned()), &source[..len]);
+        assert_eq!(v.to_string(), &source[..len]);
+        assert_eq!(format!("{v:?}"), format!("{:?}", &source[..len]));
+    }
+
+    let v = PackedOurString::<RcBytes, 15>::from("\x7f\x01\x00\x7f\x40\x20\x10\x08\x04\x02\x01\x7f\x7e\x7d\x7c\x7b\x7a");
+    assert_eq!(v.is_packed(), true);
+    assert_eq!(v, "\x7f\x01\x00\x7f\x40\x20\x10\x08\x04\x02\x01\x7f\x7e\x7d\x7c\x7b\x7a");
+
+    let v = PackedOurString::<RcBytes, 15>::from("héllo wörld again");
+    assert_eq!(v.is_packed(), false);
+    assert_eq!(v.as_str(), Some("héllo wörld again"));
+
+    const X: PackedOurString<RcBytes, 15> = PackedOurString::new();
+    assert_eq!(X.is_empty(), true);
+    assert_eq!(PackedOurString::<RcBytes, 15>::default(), "");
+}
+
+#[test]
+fn test_into_our_string() {
+    let v = PackedOurString::<RcBytes, 15>::from("column_name_17chr");
+    assert_eq!(v.is_packed(), true);
+    assert_eq!(v.clone().into_our_string::<20>(), "column_name_17chr");
+    assert_eq!(v.into_our_string::<4>(), "column_name_17chr");
+
+    let v = PackedOurString::<RcBytes, 4>::from("column_name_17chr");
+    let p = v.as_str().unwrap().as_ptr();
+    assert_eq!(v.into_our_string::<20>().as_ptr(), p);
+}
+#[cfg(not(miri))]
+proptest::proptest! {
+    #[test]
+    fn proptest_packing(a: String, b: String) {
+        let (x, y) = (PackedOurString::<RcBytes, 15>::from(a.as_str()), PackedOurString::<RcBytes, 15>::from(b.as_str()));
+        assert_eq!(x, a.as_str());
+        assert_eq!(x.len(), a.len());
+        assert_eq!(x.cmp(&y), a.cmp(&b));
+        assert_eq!(x.partial_cmp(b.as_str()), a.as_str().partial_cmp(b.as_str()));
+        assert_eq!(x == y, a == b);
+        assert_eq!(hash(&x), hash(a.as_str()));
+    }
+    #[test]
+    fn proptest_ascii_packing(a in "[ -~]{0,40}") {
+        let x = PackedOurString::<RcBytes, 22>::from(a.as_str());
+        assert_eq!(x.is_packed(), a.len() > 22 && a.len() <= 25);
+        assert_eq!(x, a.as_str());
+        assert_eq!(x.clone(), x);
+    }
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "rkyv")]
+
+use std::rc::Rc;
+
+use rkyv::rancor::Error;
+use rkyv::string::ArchivedString;
+
+use our_string::{OurString, OurBytes, ArchivedOurBytes};
+use our_string::comrades::{RcBytes, ArcBytes};
+
+#[derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize, Debug, PartialEq)]
+struct Row {
+    name: OurString<ArcBytes, 15>,
+    data: OurBytes<RcBytes, 8>,
+}
+
+#[test]
+fn test_bytes() {
+    for value in ["".as_bytes(), b"h", b"hello w", b"hello wo", b"help me obi-wan kenobi, you're my only hope"] {
+        let a = OurBytes::<RcBytes, 8>::from(value);
+        let encoded = rkyv::to_bytes::<Error>(&a).unwrap();
+        let archived = rkyv::access::<ArchivedOurBytes, Error>(&encoded).unwrap();
+        assert_eq!(archived.as_slice(), value);
+        assert_eq!(*archived, a);
+        let b: OurBytes<Rc<[u8]>, 4> = rkyv::deserialize::<_, Error>(archived).unwrap();
+        assert_eq!(b, value);
+
+        let inline = archived.as_ptr() as usize >= archived as *const ArchivedOurBytes as usize
+            && archived.as_ptr() as usize + archived.len() <= archived as *const ArchivedOurBytes as usize + size_of::<ArchivedOurBytes>();
+        assert_eq!(inline, value.len() <= 7);
+    }
+}
+
+#[test]
+fn test_string() {
+    for value in ["", "h", "hello w", "hello wo", "help me obi-wan kenobi, you're my only hope"] {
+        let a = OurString::<RcBytes, 8>::from(value);
+        let encoded = rkyv::to_bytes::<Error>(&a).unwrap();
+        let archived = rkyv::access::<ArchivedString, Error>(&encoded).unwrap();
+        assert_eq!(archived.as_str(), value);
+        let b: OurString<ArcBytes, 4> = rkyv::deserialize::<_, Error>(archived).unwrap();
+        assert_eq!(b, value);
+    }
+}
+
+#[test]
+fn test_struct() {
+    let row = Row { name: OurString::from("some column name"), data: OurBytes::from([1u8, 2, 3].as_slice()) };
+    let encoded = rkyv::to_bytes::<Error>(&row).unwrap();
+    let archived = rkyv::access::<ArchivedRow, Error>(&encoded).unwrap();
+    assert_eq!(archived.name, "some column name");
+    assert_eq!(archived.data, [1u8, 2, 3].as_slice());
+    assert_eq!(rkyv::deserialize::<Row, Error>(archived).unwrap(), row);
+}
+
+#[test]
+fn test_invalid() {
+    let mut encoded = rkyv::to_bytes::<Error>(&OurBytes::<RcBytes, 8>::from(b"help me obi-wan kenobi".as_slice())).unwrap();
+    let archived = rkyv::access::<ArchivedOurBytes, Error>(&encoded).unwrap();
+    let root = archived as *const ArchivedOurBytes as usize - encoded.as_ptr() as usize;
+
+    // the outline relative pointer is the first field (of the first field), and has the same archived width as the length which follows it;
+    // filling it with 0x7f makes it point far past the end of the buffer regardless of endianness
+    let ptr_size = size_of::<rkyv::vec::ArchivedVec<u8>>() / 2;
+    encoded[root..root + ptr_size].fill(0x7f);
+    assert!(rkyv::access::<ArchivedOurBytes, Error>(&encoded).is_err());
+}
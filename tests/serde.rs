@@ -0,0 +1,52 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg(feature = "serde")]
+
+use std::rc::Rc;
+
+use serde_test::{Token, assert_tokens, assert_de_tokens, assert_de_tokens_error};
+
+use our_string::{OurString, OurBytes, ThinOurString, UmbraOurString, PackedOurString};
+use our_string::comrades::{RcBytes, ArcBytes};
+
+#[test]
+fn test_string() {
+    assert_tokens(&OurString::<RcBytes, 8>::from("hello"), &[Token::Str("hello")]);
+    assert_tokens(&OurString::<Rc<[u8]>, 8>::from("hello world"), &[Token::Str("hello world")]);
+    assert_de_tokens(&OurString::<RcBytes, 8>::from("hello world"), &[Token::String("hello world")]);
+    assert_de_tokens(&OurString::<RcBytes, 8>::from("hello world"), &[Token::BorrowedStr("hello world")]);
+    assert_de_tokens(&OurString::<RcBytes, 8>::from("hello world"), &[Token::Bytes(b"hello world")]);
+    assert_de_tokens_error::<OurString<RcBytes, 8>>(&[Token::Bytes(&[255, 0])], "invalid value: byte array, expected a string");
+
+    assert_tokens(&ThinOurString::<RcBytes>::from("hello world"), &[Token::Str("hello world")]);
+    assert_tokens(&UmbraOurString::<ArcBytes>::from("hello world"), &[Token::Str("hello world")]);
+    assert_tokens(&PackedOurString::<RcBytes, 4>::from("hello"), &[Token::Str("hello")]);
+}
+
+#[test]
+fn test_bytes() {
+    assert_tokens(&OurBytes::<RcBytes, 8>::from([1u8, 2, 3].as_slice()), &[Token::Bytes(&[1, 2, 3])]);
+    assert_tokens(&OurBytes::<Rc<Vec<u8>>, 2>::from([1u8, 2, 3].as_slice()), &[Token::Bytes(&[1, 2, 3])]);
+    assert_de_tokens(&OurBytes::<RcBytes, 8>::from([1u8, 2, 3].as_slice()), &[Token::ByteBuf(&[1, 2, 3])]);
+    assert_de_tokens(&OurBytes::<RcBytes, 8>::from([1u8, 2, 3].as_slice()), &[Token::BorrowedBytes(&[1, 2, 3])]);
+    assert_de_tokens(&OurBytes::<RcBytes, 8>::from([1u8, 2, 3].as_slice()), &[Token::Seq { len: Some(3) }, Token::U8(1), Token::U8(2), Token::U8(3), Token::SeqEnd]);
+
+    assert_tokens(&RcBytes::from([1u8, 2, 3].as_slice()), &[Token::Bytes(&[1, 2, 3])]);
+    assert_tokens(&ArcBytes::from([1u8, 2, 3].as_slice()), &[Token::Bytes(&[1, 2, 3])]);
+}
+
+#[test]
+fn test_json() {
+    let a: OurString<RcBytes, 8> = serde_json::from_str("\"hello\"").unwrap();
+    assert_eq!(a, "hello");
+    assert_eq!(a.is_inline(), true);
+    let b: OurString<RcBytes, 8> = serde_json::from_str("\"hello world\"").unwrap();
+    assert_eq!(b, "hello world");
+    assert_eq!(b.is_inline(), false);
+    let c: OurString<RcBytes, 8> = serde_json::from_str("\"escaped \\\"string\\\"\"").unwrap();
+    assert_eq!(c, "escaped \"string\"");
+    assert_eq!(serde_json::to_string(&b).unwrap(), "\"hello world\"");
+
+    let d: OurBytes<ArcBytes, 8> = serde_json::from_str("[1,2,3]").unwrap();
+    assert_eq!(d, [1u8, 2, 3].as_slice());
+    assert_eq!(serde_json::to_string(&d).unwrap(), "[1,2,3]");
+}
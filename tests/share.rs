@@ -0,0 +1,133 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+use our_string::share::{ShareEncoder, ShareDecoder, ShareError};
+use our_string::{OurString, OurBytes};
+use our_string::comrades::{RcBytes, ArcBytes};
+
+#[test]
+fn test_sharing() {
+    let a = OurString::<RcBytes, 8>::from("hello world, how are you?");
+    let b = OurString::<RcBytes, 8>::from("hello world, how are you?"); // equal but not shared
+    let c = OurString::<RcBytes, 8>::from("small");
+
+    let mut encoder = ShareEncoder::new();
+    for _ in 0..10000 {
+        encoder.write_str(&a.clone());
+        encoder.write_str(&b);
+        encoder.write_str(&c);
+    }
+    let encoded = encoder.finish();
+    assert!(encoded.len() < 10000 * (2 + 2 + 6) + 2 * (a.len() + 2));
+
+    let mut decoder = ShareDecoder::<RcBytes>::new(&encoded);
+    let (aa, bb, cc) = (decoder.read_str::<8>().unwrap(), decoder.read_str::<8>().unwrap(), decoder.read_str::<8>().unwrap());
+    assert_eq!((&aa, &bb, &cc), (&a, &b, &c));
+    assert_ne!(aa.as_ptr(), bb.as_ptr());
+    for _ in 1..10000 {
+        assert_eq!(decoder.read_str::<8>().unwrap().as_ptr(), aa.as_ptr());
+        assert_eq!(decoder.read_str::<8>().unwrap().as_ptr(), bb.as_ptr());
+        assert_eq!(decoder.read_str::<4>().unwrap(), "small");
+    }
+    assert!(decoder.is_finished());
+    assert_eq!(decoder.read_str::<8>(), Err(ShareError::UnexpectedEnd));
+}
+
+#[test]
+fn test_short_shared() {
+    let a = OurBytes::<Arc<[u8]>, 8>::from(Arc::<[u8]>::from([1u8, 2].as_slice()));
+    let mut encoder = ShareEncoder::new();
+    encoder.write_bytes(&a);
+    encoder.write_bytes(&a);
+    encoder.write_bytes(&OurBytes::<Arc<[u8]>, 0>::from([1u8, 2, 3].as_slice()));
+    let encoded = encoder.finish();
+
+    let mut decoder = ShareDecoder::<Arc<[u8]>>::new(&encoded);
+    let x = decoder.read_bytes::<8>().unwrap();
+    let y = decoder.read_bytes::<8>().unwrap();
+    let z = decoder.read_bytes::<8>().unwrap();
+    assert_eq!(x, [1u8, 2].as_slice());
+    assert_eq!(x.as_ptr(), y.as_ptr());
+    assert_eq!(z, [1u8, 2, 3].as_slice());
+    assert!(decoder.is_finished());
+}
+
+#[test]
+fn test_distinct_empty() {
+    // distinct empty vecs share a dangling pointer, but are not the same handle
+    let a = OurBytes::<Rc<Vec<u8>>, 0>::from(Rc::new(Vec::new()));
+    let b = OurBytes::<Rc<Vec<u8>>, 0>::from(Rc::new(Vec::new()));
+    let mut encoder = ShareEncoder::new();
+    encoder.write_bytes(&a);
+    encoder.write_bytes(&b);
+    encoder.write_bytes(&a);
+    let encoded = encoder.finish();
+
+    let mut decoder = ShareDecoder::<Rc<Vec<u8>>>::new(&encoded);
+    let (x, y, z) = (decoder.read_bytes::<0>().unwrap(), decoder.read_bytes::<0>().unwrap(), decoder.read_bytes::<0>().unwrap());
+    assert_eq!((x.ptr_eq(&y), x.ptr_eq(&z)), (false, true));
+    assert!(decoder.is_finished());
+}
+
+#[test]
+fn test_utf8() {
+    let a = OurBytes::<ArcBytes, 2>::from([255u8, 0, 1].as_slice());
+    let mut encoder = ShareEncoder::new();
+    encoder.write_bytes(&a);
+    encoder.write_bytes(&a);
+    let encoded = encoder.finish();
+
+    let mut decoder = ShareDecoder::<ArcBytes>::new(&encoded);
+    assert_eq!(decoder.read_bytes::<2>().unwrap(), a);
+    assert!(matches!(decoder.read_str::<2>(), Err(ShareError::InvalidUtf8(_))));
+}
+
+#[test]
+fn test_utf8_mixed() {
+    let a = OurBytes::<ArcBytes, 2>::from("héllo".as_bytes());
+    let mut encoder = ShareEncoder::new();
+    for _ in 0..4 {
+        encoder.write_bytes(&a);
+    }
+    let encoded = encoder.finish();
+
+    let mut decoder = ShareDecoder::<ArcBytes>::new(&encoded);
+    let x = decoder.read_bytes::<2>().unwrap();
+    let y = decoder.read_str::<2>().unwrap();
+    let z = decoder.read_str::<2>().unwrap();
+    let w = decoder.read_bytes::<2>().unwrap();
+    assert_eq!((y.as_str(), z.as_str(), &*w), ("héllo", "héllo", "héllo".as_bytes()));
+    assert_eq!((x.as_ptr(), y.as_ptr(), z.as_ptr()), (w.as_ptr(), w.as_ptr(), w.as_ptr()));
+    assert!(decoder.is_finished());
+}
+
+#[test]
+fn test_invalid() {
+    assert_eq!(ShareDecoder::<Rc<[u8]>>::new(&[]).read_bytes::<4>(), Err(ShareError::UnexpectedEnd));
+    assert_eq!(ShareDecoder::<Rc<[u8]>>::new(&[0x80]).read_bytes::<4>(), Err(ShareError::UnexpectedEnd));
+    assert_eq!(ShareDecoder::<Rc<[u8]>>::new(&[3]).read_bytes::<4>(), Err(ShareError::InvalidHeader));
+    assert_eq!(ShareDecoder::<Rc<[u8]>>::new(&[2]).read_bytes::<4>(), Err(ShareError::InvalidReference));
+    assert_eq!(ShareDecoder::<Rc<[u8]>>::new(&[4 << 2]).read_bytes::<4>(), Err(ShareError::UnexpectedEnd));
+    assert_eq!(ShareDecoder::<Rc<[u8]>>::new(&[0xff; 11]).read_bytes::<4>(), Err(ShareError::InvalidHeader));
+}
+#[cfg(not(miri))]
+proptest::proptest! {
+    #[test]
+    fn proptest_roundtrip(values: Vec<(String, u8)>) {
+        let values = values.into_iter().map(|(s, n)| (OurString::<RcBytes, 4>::from(s.as_str()), n % 4)).collect::<Vec<_>>();
+        let mut encoder = ShareEncoder::new();
+        for (value, n) in values.iter() {
+            for _ in 0..*n {
+                encoder.write_str(value);
+            }
+        }
+        let encoded = encoder.finish();
+        let mut decoder = ShareDecoder::<RcBytes>::new(&encoded);
+        for (value, n) in values.iter() {
+            for _ in 0..*n {
+                assert_eq!(&decoder.read_str::<4>().unwrap(), value);
+            }
+        }
+        assert!(decoder.is_finished());
+    }
+}
@@ -0,0 +1,110 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use std::sync::Arc;
+use std::rc::Rc;
+
+use our_string::OurSlice;
+use our_string::comrades::{RcSlice, ArcSlice, RcSliceW, ArcSliceW};
+
+#[test]
+fn test_traits() {
+    macro_rules! assert_impl {
+        ($t:ty : $($tr:tt)*) => {{
+            fn checker<T: $($tr)*>() {}
+            checker::<$t>();
+        }};
+    }
+
+    assert_impl!(OurSlice<u32, RcSlice<u32>, 4> : Clone + Default + core::fmt::Debug + Eq + Ord + core::hash::Hash + core::ops::Deref<Target = [u32]> + AsRef<[u32]> + core::borrow::Borrow<[u32]>);
+    assert_impl!(OurSlice<f32, ArcSlice<f32>, 4> : Clone + Default + core::fmt::Debug + PartialEq + PartialOrd + Send + Sync);
+    assert_impl!(OurSlice<(u16, u16), Rc<[(u16, u16)]>, 4> : Clone + Eq + Ord + core::hash::Hash);
+    assert_impl!(ArcSlice<u64> : Send + Sync + Clone + Eq + Ord + core::hash::Hash + core::ops::Deref<Target = [u64]>);
+}
+
+#[test]
+fn test_inlining() {
+    for value in [[].as_slice(), &[1u32], &[1, 2, 3], &[1, 2, 3, 4], &[1, 2, 3, 4, 5], &[u32::MAX; 100]] {
+        let v = OurSlice::<u32, RcSlice<u32>, 4>::from(value);
+        assert_eq!(v, value);
+        assert_eq!(&*v, value);
+        assert_eq!(v.is_inline(), value.len() <= 4);
+        assert_eq!(v.as_ptr() as usize % align_of::<u32>(), 0);
+        let vv = v.clone();
+        assert_eq!(vv, v);
+        assert_eq!(vv.as_ptr() == v.as_ptr(), value.len() > 4);
+    }
+
+    let v = OurSlice::<(u16, u16), Arc<[(u16, u16)]>, 2>::from([(1, 2), (3, 4), (5, 6)].as_slice());
+    assert_eq!(v.is_inline(), false);
+    assert_eq!(v.convert::<3>().as_slice(), [(1, 2), (3, 4), (5, 6)]);
+
+    let v = OurSlice::<f32, ArcSlice<f32>, 2>::from_comrade(ArcSlice::from([1.5f32].as_slice()));
+    assert_eq!(v.is_inline(), false);
+    assert_eq!(v, [1.5f32].as_slice());
+    assert_eq!(format!("{v:?}"), "[1.5]");
+
+    assert_eq!(OurSlice::<u64, RcSlice<u64>, 8>::new().is_empty(), true);
+}
+
+#[test]
+fn test_alignment() {
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    #[repr(align(64))]
+    struct Line(u8);
+
+    for len in [0, 1, 2, 7, 100] {
+        let value = (0..len).map(|i| Line(i as u8)).collect::<Vec<_>>();
+        let a = RcSlice::<Line>::from(value.as_slice());
+        assert_eq!(&*a, value.as_slice());
+        assert_eq!(a.as_ptr() as usize % 64, 0);
+        let b = ArcSlice::<u128>::from((0..len as u128).collect::<Vec<_>>().as_slice());
+        assert_eq!(b.as_ptr() as usize % align_of::<u128>(), 0);
+        assert_eq!(b.iter().copied().eq(0..len as u128), true);
+    }
+
+    let zst = RcSlice::<()>::from([(); 1000].as_slice());
+    assert_eq!(zst.len(), 1000);
+}
+
+#[test]
+fn test_weak() {
+    let a = OurSlice::<u32, RcSliceW<u32>, 2>::from([1u32, 2, 3].as_slice());
+    let w = a.downgrade();
+    assert_eq!(w.upgrade().unwrap().as_ptr(), a.as_ptr());
+    drop(a);
+    assert_eq!(w.upgrade().is_none(), true);
+
+    let a = OurSlice::<u32, ArcSliceW<u32>, 4>::from([1u32, 2, 3].as_slice());
+    let w = a.downgrade();
+    drop(a);
+    assert_eq!(w.upgrade().unwrap(), [1u32, 2, 3].as_slice());
+}
+
+#[test]
+fn test_nan() {
+    let a = OurSlice::<f32, ArcSlice<f32>, 1>::from([1.0, f32::NAN].as_slice());
+    let b = a.clone();
+    assert_eq!((a.ptr_eq(&b), a == b, a.partial_cmp(&b)), (true, false, None));
+    let c = OurSlice::<f32, ArcSlice<f32>, 1>::from([1.0, 2.0].as_slice());
+    assert_eq!((c == c.clone(), c.partial_cmp(&c.clone())), (true, Some(std::cmp::Ordering::Equal)));
+}
+
+#[test]
+fn test_compare_elements() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static COMPARISONS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Clone, Copy)]
+    struct Counted(u8);
+    impl PartialEq for Counted {
+        fn eq(&self, other: &Self) -> bool {
+            COMPARISONS.fetch_add(1, Ordering::Relaxed);
+            self.0 == other.0
+        }
+    }
+
+    // the identity fast path is only taken for bytes, since other element types may not have reflexive equality
+    let a = OurSlice::<Counted, ArcSlice<Counted>, 1>::from([Counted(1), Counted(2), Counted(3)].as_slice());
+    assert_eq!((a == a.clone(), COMPARISONS.load(Ordering::Relaxed)), (true, 3));
+}
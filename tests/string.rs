@@ -1,4 +1,6 @@
-use std::cmp::{PartialEq, Eq, PartialOrd, Ord};
+#![allow(clippy::bool_assert_comparison, clippy::needless_borrow, clippy::explicit_auto_deref)]
+
+use std::cmp::{Ordering, PartialEq, Eq, PartialOrd, Ord};
 use std::hash::{DefaultHasher, Hash, Hasher};
 use std::fmt::{Debug, Display};
 use std::borrow::Borrow;
@@ -7,7 +9,8 @@ use std::ops::Deref;
 use std::sync::Arc;
 use std::rc::Rc;
 
-use our_string::{OurString, Comrade};
+use our_string::{OurString, Comrade, ById, HeapUsage};
+use our_string::comrades::{RcBytes, ArcBytes, RcBytesW, RcStr, ArcStr};
 
 fn hash<T: Hash>(t: &T) -> u64 {
     let mut s = DefaultHasher::new();
@@ -401,3 +404,161 @@ fn test_utf8() {
     let x = our_string::OurBytes::<Rc<[u8]>, 8>::from([255, 127, 56].as_slice());
     OurString::from_utf8(x).unwrap_err();
 }
+
+#[test]
+fn test_downgrade() {
+    let a = OurString::<RcBytesW, 8>::from("hello");
+    let w = a.downgrade();
+    drop(a);
+    assert_eq!(w.upgrade().unwrap(), "hello");
+    assert_eq!(w.upgrade().unwrap().is_inline(), true);
+
+    let a = OurString::<RcBytesW, 8>::from("hello world");
+    let w = a.downgrade();
+    assert_eq!(w.upgrade().unwrap().as_ptr(), a.as_ptr());
+    drop(a);
+    assert_eq!(w.upgrade().is_none(), true);
+
+    let a = OurString::<Arc<[u8]>, 4>::from("hello world");
+    let w = a.downgrade();
+    assert_eq!(w.clone().upgrade().unwrap().as_ptr(), a.as_ptr());
+    drop(a);
+    assert_eq!(w.upgrade().is_none(), true);
+}
+
+#[test]
+fn test_convert_comrade() {
+    let a = OurString::<RcBytes, 8>::from("hello world");
+    let ptr = a.as_ptr();
+    let b: OurString<ArcBytes, 8> = a.convert_comrade();
+    assert_eq!(b.as_ptr(), ptr);
+    let b = std::thread::spawn(move || b).join().unwrap();
+    assert_eq!(b, "hello world");
+
+    let c: OurString<RcBytes, 8> = b.clone().convert_comrade();
+    assert_ne!(c.as_ptr(), ptr);
+    assert_eq!(c, "hello world");
+
+    let d: OurString<Rc<[u8]>, 16> = OurString::<RcBytes, 8>::from("hello").convert_comrade();
+    assert_eq!(d.is_inline(), true);
+    assert_eq!(d, "hello");
+}
+
+#[test]
+fn test_from_str_comrade() {
+    let shared: Arc<str> = Arc::from("hello world, this is a long string");
+    let a = OurString::<ArcStr, 8>::from(shared.clone());
+    assert_eq!(a, "hello world, this is a long string");
+    assert_eq!(a.as_ptr(), shared.as_ptr());
+    assert_eq!(a.is_inline(), false);
+    assert_eq!(Arc::ptr_eq(&a.into_comrade().unwrap().into_str().unwrap(), &shared), true);
+
+    let b = OurString::<RcStr, 8>::from("hi");
+    assert_eq!(b.is_inline(), true);
+    let c = OurString::<RcStr, 8>::from("hello world");
+    assert_eq!(c.is_inline(), false);
+    let w = c.downgrade();
+    assert_eq!(w.upgrade().unwrap().as_ptr(), c.as_ptr());
+    let d: OurString<RcBytes, 4> = c.clone().convert_comrade();
+    assert_eq!(d, "hello world");
+
+    assert_eq!(OurString::from_utf8(our_string::OurBytes::<RcStr, 4>::from(b"hello".as_slice())).unwrap(), "hello");
+    let e = our_string::OurBytes::<RcStr, 4>::from([0xff; 10].as_slice());
+    assert_eq!(e, [0xff; 10].as_slice());
+    assert_eq!(e.clone().into_comrade().unwrap().into_str().unwrap_err(), [0xff; 10]);
+    assert_eq!(OurString::from_utf8(e).is_err(), true);
+}
+
+#[test]
+fn test_convert_compact() {
+    let a = OurString::<RcBytes, 4>::from("hello");
+    let b: OurString<RcBytes, 8> = a.clone().convert_compact();
+    assert_eq!(b.is_inline(), true);
+    assert_eq!(b, "hello");
+    let c: OurString<RcBytes, 4> = a.clone().convert_compact();
+    assert_eq!(c.as_ptr(), a.as_ptr());
+
+    let d = OurString::<RcBytes, 8>::from("hello").try_convert::<4>().unwrap_err();
+    assert_eq!(d.is_inline(), true);
+    assert_eq!(d, "hello");
+    let e = d.try_convert::<5>().unwrap();
+    assert_eq!(e.is_inline(), true);
+    assert_eq!(a.clone().try_convert::<2>().unwrap().as_ptr(), a.as_ptr());
+}
+
+#[test]
+fn test_canonical() {
+    let a = OurString::<RcStr, 8>::from_comrade_inlining(Rc::from("hello"));
+    assert_eq!((a.as_str(), a.is_inline(), a.is_canonical()), ("hello", true, true));
+    let b = OurString::<RcStr, 4>::from_comrade_inlining(Rc::from("hello"));
+    assert_eq!((b.as_str(), b.is_inline(), b.is_canonical()), ("hello", false, true));
+
+    let mut c = OurString::<RcStr, 8>::from(Rc::<str>::from("hello"));
+    assert_eq!(c.is_canonical(), false);
+    c.normalize();
+    assert_eq!((c.as_str(), c.is_inline(), c.is_canonical()), ("hello", true, true));
+    c.debug_assert_canonical();
+}
+
+#[test]
+fn test_ptr_eq() {
+    let a = OurString::<ArcStr, 4>::from("hello world");
+    let b = a.clone();
+    let c = OurString::<ArcStr, 4>::from("hello world");
+    assert_eq!((a.ptr_eq(&b), a.ptr_eq(&c), a == b, a == c, a.cmp(&b), a.cmp(&c)), (true, false, true, true, Ordering::Equal, Ordering::Equal));
+    assert_eq!((a.as_ptr() == b.as_ptr(), a == b, b.as_str() == a, a.partial_cmp(&b), PartialOrd::partial_cmp(&b.as_str(), &a)), (true, true, true, Some(Ordering::Equal), Some(Ordering::Equal)));
+
+    let d = OurString::<ArcStr, 4>::from("hi");
+    assert_eq!((d.ptr_eq(&d.clone()), d == d.clone()), (false, true));
+}
+
+#[test]
+fn test_by_id() {
+    let a = OurString::<RcBytes, 4>::from("hello world");
+    let b = OurString::<RcBytes, 4>::from("hello world");
+    let c = OurString::<RcBytes, 4>::from("hi");
+    assert_eq!(a, b);
+    assert_ne!(ById(a.clone()), ById(b.clone()));
+    assert_eq!(ById(a.clone()), ById(a.clone()));
+    assert_eq!(hash(&ById(a.clone())), hash(&ById(a.clone())));
+    assert_eq!(ById(c.clone()), ById(OurString::from("hi")));
+    assert_eq!(hash(&ById(c.clone())), hash(&ById(OurString::<RcBytes, 4>::from("hi"))));
+    assert_ne!(ById(OurString::<RcBytes, 4>::from("hi")), ById(OurString::from_utf8(our_string::OurBytes::from(RcBytes::from_slice(b"hi"))).unwrap()));
+
+    let set = [a.clone(), b.clone(), a.clone(), c.clone(), c.clone()].into_iter().map(ById).collect::<std::collections::HashSet<_>>();
+    assert_eq!(set.len(), 3);
+    assert!(set.contains(&ById(a.clone())) && set.contains(&ById(b)) && set.contains(&ById(c)));
+    assert!(!set.contains(&ById(OurString::from("hello world"))));
+    assert_eq!(ById(a.clone()).into_inner(), a);
+    assert_eq!(format!("{:?}", ById(a)), "ById(\"hello world\")");
+}
+
+#[test]
+fn test_introspection() {
+    let a = OurString::<ArcBytes, 8>::from("hello");
+    let b = OurString::<ArcBytes, 8>::from("hello world");
+    assert_eq!((a.is_inline(), is_inline(&a), a.inline_capacity(), a.strong_count(), a.heap_size()), (true, true, 8, None, 0));
+    assert_eq!((b.is_inline(), is_inline(&b), b.strong_count(), b.heap_size()), (false, false, Some(1), 2 * size_of::<usize>() + 11));
+    let bb = b.clone();
+    assert_eq!((b.strong_count(), bb.strong_count()), (Some(2), Some(2)));
+
+    let mut usage = HeapUsage::new();
+    usage.extend([&a, &b, &bb]);
+    assert_eq!(usage.add_string(&b.clone()), 0);
+    assert_eq!((usage.total(), usage.allocations()), (b.heap_size(), 1));
+}
+
+#[test]
+fn test_into_comrade() {
+    let shared = Rc::<str>::from("hello world");
+    let a = OurString::<RcStr, 4>::from(shared.clone());
+    assert_eq!(a.as_comrade().map(|x| x.as_ptr() == shared.as_ptr()), Some(true));
+    assert_eq!(Rc::ptr_eq(&a.clone().into_comrade().unwrap().into_str().unwrap(), &shared), true);
+    assert_eq!(Rc::ptr_eq(&a.into_comrade_or_alloc().into_str().unwrap(), &shared), true);
+
+    let b = OurString::<RcStr, 4>::from("hi");
+    assert_eq!(b.as_comrade().is_none(), true);
+    let b = b.into_comrade().unwrap_err();
+    assert_eq!(b, "hi");
+    assert_eq!(&*b.into_comrade_or_alloc(), b"hi");
+}
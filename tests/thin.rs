@@ -0,0 +1,150 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use std::cmp::{PartialEq, Eq, PartialOrd, Ord};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::fmt::{Debug, Display};
+use std::borrow::Borrow;
+use std::mem::size_of;
+use std::ops::Deref;
+
+use our_string::{ThinOurString, ThinOurBytes, ThinComrade};
+use our_string::comrades::{RcBytes, ArcBytes};
+
+fn hash<T: Hash>(t: &T) -> u64 {
+    let mut s = DefaultHasher::new();
+    t.hash(&mut s);
+    s.finish()
+}
+
+fn is_inline<T: ThinComrade>(v: &ThinOurString<T>) -> bool {
+    let l = v.len();
+    let s = v.as_str() as *const str as *const () as usize;
+    let v = v as *const ThinOurString<T> as *const () as usize;
+    s >= v && s + l <= v + size_of::<ThinOurString<T>>()
+}
+
+#[test]
+fn test_sizes() {
+    assert_eq!(size_of::<ThinOurString<RcBytes>>(), size_of::<usize>());
+    assert_eq!(size_of::<ThinOurString<ArcBytes>>(), size_of::<usize>());
+    assert_eq!(size_of::<Option<ThinOurString<RcBytes>>>(), size_of::<usize>());
+    assert_eq!(size_of::<ThinOurBytes<RcBytes>>(), size_of::<usize>());
+    assert_eq!(size_of::<Option<ThinOurBytes<ArcBytes>>>(), size_of::<usize>());
+}
+
+#[test]
+fn test_traits() {
+    macro_rules! assert_impl {
+        ($t:ty : $($tr:tt)*) => {{
+            fn checker<T: $($tr)*>() {}
+            checker::<$t>();
+        }};
+    }
+    macro_rules! assert_not_impl {
+        ($t:ty : $($tr:tt)*) => {
+            const _: fn() -> () = || {
+                struct Check<T: ?Sized>(T);
+                trait Foo<A> { fn foo() {} }
+                impl<T: ?Sized> Foo<()> for Check<T> {}
+                impl<T: ?Sized + $($tr)*> Foo<u8> for Check<T> {}
+                <Check::<$t> as Foo<_>>::foo()
+            };
+        };
+    }
+
+    assert_impl!(ThinOurString<RcBytes> : Hash + Clone + Debug + Display + PartialEq + Eq + PartialOrd + Ord + Default + AsRef<str> + Borrow<str> + Deref<Target = str> + for<'a> From<&'a str>);
+    assert_impl!(ThinOurString<ArcBytes> : Send + Sync + Hash + Clone + Debug + Display + PartialEq + Eq + PartialOrd + Ord + Default + AsRef<str> + Borrow<str> + Deref<Target = str> + for<'a> From<&'a str>);
+    assert_impl!(ThinOurBytes<ArcBytes> : Send + Sync + Hash + Clone + Debug + PartialEq + Eq + PartialOrd + Ord + Default + AsRef<[u8]> + Borrow<[u8]> + Deref<Target = [u8]> + for<'a> From<&'a [u8]> + From<ArcBytes>);
+    assert_not_impl!(ThinOurString<RcBytes> : Send);
+    assert_not_impl!(ThinOurString<RcBytes> : Sync);
+}
+
+#[test]
+fn test_new_default() {
+    const X: ThinOurString<RcBytes> = ThinOurString::new();
+    assert_eq!(X.len(), 0);
+    assert_eq!(X.is_empty(), true);
+    assert_eq!(is_inline(&X), true);
+
+    assert_eq!(ThinOurString::<ArcBytes>::default().len(), 0);
+    assert_eq!(ThinOurString::<ArcBytes>::default().as_str().is_empty(), true);
+}
+
+#[test]
+fn test_inlining() {
+    let limit = size_of::<usize>() - 1;
+    let source = "hello from the other side";
+    for len in 0..source.len() {
+        let v = ThinOurString::<RcBytes>::from(&source[..len]);
+        assert_eq!(v, &source[..len]);
+        assert_eq!(is_inline(&v), len <= limit);
+        let v = ThinOurString::<ArcBytes>::from(&source[..len]);
+        assert_eq!(v, &source[..len]);
+        assert_eq!(is_inline(&v), len <= limit);
+    }
+}
+
+#[test]
+fn test_clone() {
+    let a = ThinOurString::<RcBytes>::from("hello world again");
+    let b = a.clone();
+    assert_eq!(a, b);
+    assert_eq!(a.as_str() as *const str, b.as_str() as *const str);
+    drop(a);
+    assert_eq!(b, "hello world again");
+
+    let a = ThinOurString::<RcBytes>::from("hi");
+    let b = a.clone();
+    assert_eq!(a, b);
+    assert_ne!(a.as_str() as *const str, b.as_str() as *const str);
+
+    let a = ThinOurString::<ArcBytes>::from("hello world again");
+    let b = a.clone();
+    std::thread::spawn(move || assert_eq!(b, "hello world again")).join().unwrap();
+    assert_eq!(a, "hello world again");
+}
+#[cfg(not(miri))]
+proptest::proptest! {
+    #[test]
+    fn proptest_clone(s: String) {
+        let a = ThinOurString::<RcBytes>::from(s.as_str());
+        let b = a.clone();
+        assert_eq!(a, s.as_str());
+        assert_eq!(b, s.as_str());
+        assert_eq!(is_inline(&a), s.len() < size_of::<usize>());
+        if !is_inline(&a) {
+            assert_eq!(a.as_str() as *const str, b.as_str() as *const str);
+        }
+    }
+}
+
+#[test]
+fn test_from_comrade() {
+    let a = RcBytes::from(b"hi".as_slice());
+    let b = ThinOurBytes::from(a.clone());
+    assert_eq!(b, b"hi".as_slice());
+    assert_eq!(b.as_ptr(), a.as_ptr());
+}
+
+#[test]
+fn test_utf8() {
+    let a = ThinOurBytes::<RcBytes>::from("hello world".as_bytes());
+    let b = ThinOurString::from_utf8(a.clone()).unwrap();
+    assert_eq!(b, "hello world");
+    assert_eq!(b.into_bytes().as_ptr(), a.as_ptr());
+    ThinOurString::from_utf8(ThinOurBytes::<RcBytes>::from([255, 127, 56].as_slice())).unwrap_err();
+}
+
+#[test]
+fn test_hash_comparison() {
+    for (a, b) in [("", "a"), ("abc", "abd"), ("hello", "hello world"), ("hello world", "hello world!")] {
+        let (x, y) = (ThinOurString::<RcBytes>::from(a), ThinOurString::<RcBytes>::from(b));
+        assert_eq!(hash(&x), hash(&a));
+        assert_eq!(x.cmp(&y), a.cmp(b));
+        assert_eq!(x.partial_cmp(&y), a.partial_cmp(b));
+        assert_eq!(x == y, a == b);
+        assert_eq!(x, x.clone());
+        assert_eq!(format!("{x}"), a);
+        assert_eq!(format!("{x:?}"), format!("{a:?}"));
+    }
+}
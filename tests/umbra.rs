@@ -0,0 +1,133 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use std::cmp::{PartialEq, Eq, PartialOrd, Ord};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::fmt::{Debug, Display};
+use std::borrow::Borrow;
+use std::mem::size_of;
+use std::ops::Deref;
+use std::rc::Rc;
+
+use our_string::{UmbraOurString, UmbraOurBytes, Comrade};
+use our_string::comrades::{RcBytes, ArcBytes};
+
+fn hash<T: Hash>(t: &T) -> u64 {
+    let mut s = DefaultHasher::new();
+    t.hash(&mut s);
+    s.finish()
+}
+
+fn is_inline<T: Comrade>(v: &UmbraOurString<T>) -> bool {
+    let l = v.len();
+    let s = v.as_str() as *const str as *const () as usize;
+    let v = v as *const UmbraOurString<T> as *const () as usize;
+    s >= v && s + l <= v + size_of::<UmbraOurString<T>>()
+}
+
+#[test]
+fn test_sizes() {
+    assert_eq!(size_of::<UmbraOurString<RcBytes>>(), 16);
+    assert_eq!(size_of::<UmbraOurString<ArcBytes>>(), 16);
+    assert_eq!(size_of::<UmbraOurBytes<RcBytes>>(), 16);
+    assert_eq!(size_of::<UmbraOurString<Rc<Vec<u8>>>>(), 16);
+}
+
+#[test]
+fn test_traits() {
+    macro_rules! assert_impl {
+        ($t:ty : $($tr:tt)*) => {{
+            fn checker<T: $($tr)*>() {}
+            checker::<$t>();
+        }};
+    }
+
+    assert_impl!(UmbraOurString<RcBytes> : Hash + Clone + Debug + Display + PartialEq + PartialEq<str> + for<'a> PartialEq<&'a str> + PartialEq<String> + Eq + PartialOrd + Ord + Default + AsRef<str> + Borrow<str> + Deref<Target = str> + for<'a> From<&'a str>);
+    assert_impl!(UmbraOurString<ArcBytes> : Send + Sync + Hash + Clone + Debug + Display + PartialEq + Eq + PartialOrd + Ord + Default + AsRef<str> + Borrow<str> + Deref<Target = str> + for<'a> From<&'a str>);
+    assert_impl!(UmbraOurBytes<ArcBytes> : Send + Sync + Hash + Clone + Debug + PartialEq + PartialEq<[u8]> + for<'a> PartialEq<&'a [u8]> + PartialEq<Vec<u8>> + Eq + PartialOrd + Ord + Default + AsRef<[u8]> + Borrow<[u8]> + Deref<Target = [u8]> + for<'a> From<&'a [u8]> + From<ArcBytes>);
+}
+
+#[test]
+fn test_inlining() {
+    let source = "hello from the other side";
+    for len in 0..source.len() {
+        let v = UmbraOurString::<RcBytes>::from(&source[..len]);
+        assert_eq!(v, &source[..len]);
+        assert_eq!(v.len(), len);
+        assert_eq!(is_inline(&v), len <= 12);
+    }
+
+    const X: UmbraOurString<RcBytes> = UmbraOurString::new();
+    assert_eq!(X.is_empty(), true);
+    assert_eq!(is_inline(&X), true);
+    assert_eq!(UmbraOurString::<RcBytes>::default(), "");
+}
+
+#[test]
+fn test_from_comrade() {
+    let a = UmbraOurBytes::<RcBytes>::from(RcBytes::from(b"hello".as_slice()));
+    assert_eq!(a, b"hello".as_slice());
+    assert_ne!(a.as_ptr(), b"hello".as_ptr());
+
+    let b = RcBytes::from(b"hello world, again".as_slice());
+    let a = UmbraOurBytes::<RcBytes>::from(b.clone());
+    assert_eq!(a, b"hello world, again".as_slice());
+    assert_eq!(a.as_ptr(), b.as_ptr());
+}
+
+#[test]
+fn test_clone() {
+    let a = UmbraOurString::<RcBytes>::from("hello world again");
+    let b = a.clone();
+    assert_eq!(a, b);
+    assert_eq!(a.as_ptr(), b.as_ptr());
+    drop(a);
+    assert_eq!(b, "hello world again");
+
+    let a = UmbraOurString::<ArcBytes>::from("hello world again");
+    let b = a.clone();
+    std::thread::spawn(move || assert_eq!(b, "hello world again")).join().unwrap();
+}
+
+#[test]
+fn test_utf8() {
+    let a = UmbraOurBytes::<RcBytes>::from("hello world again".as_bytes());
+    let b = UmbraOurString::from_utf8(a.clone()).unwrap();
+    assert_eq!(b, "hello world again");
+    assert_eq!(b.into_bytes().as_ptr(), a.as_ptr());
+    UmbraOurString::from_utf8(UmbraOurBytes::<RcBytes>::from([255, 127, 56].as_slice())).unwrap_err();
+}
+
+#[test]
+fn test_comparison() {
+    let values = ["", "a", "a\0", "a\0b", "ab", "abcd", "abcd\0", "abcde", "abcdefghijkl", "abcdefghijklm", "abcdefghijkln", "abce", "b"];
+    for a in values {
+        for b in values {
+            let (x, y) = (UmbraOurString::<RcBytes>::from(a), UmbraOurString::<RcBytes>::from(b));
+            assert_eq!(x.cmp(&y), a.cmp(b), "{a:?} {b:?}");
+            assert_eq!(x == y, a == b, "{a:?} {b:?}");
+            assert_eq!(x.partial_cmp(b), a.partial_cmp(b));
+            assert_eq!(x == b, a == b);
+            assert_eq!(b == x, a == b);
+        }
+        assert_eq!(hash(&UmbraOurString::<RcBytes>::from(a)), hash(&a));
+    }
+}
+#[cfg(not(miri))]
+proptest::proptest! {
+    #[test]
+    fn proptest_comparison(a: String, b: String) {
+        let (x, y) = (UmbraOurString::<RcBytes>::from(a.as_str()), UmbraOurString::<RcBytes>::from(b.as_str()));
+        assert_eq!(x, a.as_str());
+        assert_eq!(x.cmp(&y), a.cmp(&b));
+        assert_eq!(x == y, a == b);
+        assert_eq!(x.clone() == x, true);
+        assert_eq!(hash(&x), hash(&a));
+    }
+    #[test]
+    fn proptest_bytes_comparison(a: Vec<u8>, b: Vec<u8>) {
+        let (x, y) = (UmbraOurBytes::<RcBytes>::from(a.as_slice()), UmbraOurBytes::<RcBytes>::from(b.as_slice()));
+        assert_eq!(x, a);
+        assert_eq!(x.cmp(&y), a.cmp(&b));
+        assert_eq!(x == y, a == b);
+    }
+}